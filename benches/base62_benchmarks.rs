@@ -1,5 +1,5 @@
 use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
-use snowid::{SnowID, base62_decode, base62_encode};
+use snowid::{Base62Str, SnowID, base62_decode, base62_encode, base62_encode_into};
 use std::hint::black_box;
 
 // Common test values used across benchmarks
@@ -27,6 +27,11 @@ pub fn id_generation_comparison(c: &mut Criterion) {
         b.iter(|| black_box(generator.generate_base62()));
     });
 
+    // Benchmark allocation-free base62 generation
+    group.bench_function("base62_str_generation", |b| {
+        b.iter(|| black_box(generator.generate_base62_str()));
+    });
+
     group.finish();
 }
 
@@ -41,6 +46,21 @@ pub fn base62_encoding(c: &mut Criterion) {
                 b.iter(|| black_box(base62_encode(value)));
             },
         );
+        group.bench_with_input(
+            BenchmarkId::new("base62_encode_into", value),
+            &value,
+            |b, &value| {
+                let mut buf = [0u8; 11];
+                b.iter(|| black_box(base62_encode_into(value, &mut buf).len()));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("base62_str", value),
+            &value,
+            |b, &value| {
+                b.iter(|| black_box(Base62Str::new(value)));
+            },
+        );
     }
 
     group.finish();
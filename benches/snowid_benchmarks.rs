@@ -11,11 +11,12 @@ pub fn node_bits_comparison(c: &mut Criterion) {
         let config = SnowIDConfig::builder()
             .node_bits(node_bits)
             .unwrap()
-            .build();
+            .build()
+            .unwrap();
 
         // Calculate theoretical limits for documentation
         let max_nodes = 2u32.pow(node_bits as u32);
-        let sequence_bits = 22 - node_bits; // Total bits for node+sequence is fixed at 22
+        let sequence_bits = 22 - node_bits; // The default 42-bit timestamp leaves 22 bits
         let max_sequence = 2u32.pow(sequence_bits as u32);
 
         group.bench_function(
@@ -34,7 +35,11 @@ pub fn node_bits_comparison(c: &mut Criterion) {
 
 pub fn overflow_stress_single_thread(c: &mut Criterion) {
     // Reduce sequence capacity per ms to 64 by using node_bits=16
-    let cfg = SnowIDConfig::builder().node_bits(16).unwrap().build();
+    let cfg = SnowIDConfig::builder()
+        .node_bits(16)
+        .unwrap()
+        .build()
+        .unwrap();
     let generator = SnowID::with_config(1, cfg).unwrap();
 
     let mut group = c.benchmark_group("Overflow SingleThread");
@@ -59,7 +64,11 @@ pub fn overflow_stress_single_thread(c: &mut Criterion) {
 
 pub fn overflow_stress_concurrent_lockfree(c: &mut Criterion) {
     // node_bits=16 -> sequence capacity 64 per ms, easier to hit overflow
-    let cfg = SnowIDConfig::builder().node_bits(16).unwrap().build();
+    let cfg = SnowIDConfig::builder()
+        .node_bits(16)
+        .unwrap()
+        .build()
+        .unwrap();
     let mut group = c.benchmark_group("Overflow Concurrent");
 
     for &threads in &[2usize, 4, 8] {
@@ -94,6 +103,31 @@ pub fn overflow_stress_concurrent_lockfree(c: &mut Criterion) {
     group.finish();
 }
 
+pub fn batch_generation_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Batch Generation");
+    let generator = SnowID::new(1).unwrap();
+
+    for &batch in &[64usize, 1024, 16384] {
+        let mut buf = vec![0u64; batch];
+        group.bench_function(format!("generate_into/{batch}"), |b| {
+            b.iter(|| {
+                generator.generate_into(&mut buf);
+                black_box(buf[batch - 1]);
+            });
+        });
+        group.bench_function(format!("generate_loop/{batch}"), |b| {
+            b.iter(|| {
+                for slot in buf.iter_mut() {
+                    *slot = generator.generate();
+                }
+                black_box(buf[batch - 1]);
+            });
+        });
+    }
+
+    group.finish();
+}
+
 pub fn component_extraction_benchmarks(c: &mut Criterion) {
     let mut group = c.benchmark_group("Component Extraction");
     let generator = SnowID::new(1).unwrap();
@@ -139,6 +173,7 @@ pub fn concurrent_benchmarks(c: &mut Criterion) {
     node_bits_comparison,
     concurrent_benchmarks,
     component_extraction_benchmarks,
+    batch_generation_benchmarks,
     overflow_stress_single_thread,
     overflow_stress_concurrent_lockfree
 );
@@ -7,7 +7,8 @@ fn main() {
         .epoch(1577836800000) // 2020-01-01 00:00:00 UTC
         .node_bits(16) // 16 bits for node ID = 65536 nodes
         .unwrap()
-        .build();
+        .build()
+        .unwrap();
 
     // Create generator with node ID 42
     let generator = SnowID::with_config(42, config).unwrap();
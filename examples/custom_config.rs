@@ -6,7 +6,8 @@ fn main() {
         .epoch(1577836800000)
         .node_bits(16) // 16 bits for node ID = 65,536 nodes
         .unwrap()
-        .build();
+        .build()
+        .unwrap();
 
     // Create generator with node ID 42
     let generator = SnowID::with_config(1, config).unwrap();
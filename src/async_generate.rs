@@ -0,0 +1,146 @@
+use std::time::Duration;
+
+use crate::{Clock, Reservation, SnowID, SnowIDError};
+
+/// Async generation, available with the `async` feature.
+///
+/// These methods share the same atomic state as the sync API, so sync and async
+/// callers can use one generator concurrently. When the sequence is exhausted they
+/// await a tokio timer instead of putting the worker thread to sleep.
+impl<C: Clock> SnowID<C> {
+    /// Generate a new SnowID, yielding to the runtime while waiting for the next millisecond
+    ///
+    /// # Panics
+    /// Panics under the same conditions as `generate`.
+    pub async fn generate_async(&self) -> u64 {
+        match self.try_generate_async().await {
+            Ok(id) => id,
+            Err(err) => panic!("SnowID generation failed: {err}"),
+        }
+    }
+
+    /// Generate a new SnowID asynchronously, reporting clock regressions instead of panicking
+    ///
+    /// # Returns
+    /// * `Result<u64, SnowIDError>` - New SnowID value or `ClockMovedBackwards`
+    ///   if the configured `ClockRegressionPolicy` refuses to issue an ID
+    pub async fn try_generate_async(&self) -> Result<u64, SnowIDError> {
+        if let Some(id) = self.try_fast_path() {
+            return Ok(id);
+        }
+
+        let (ts, seq, _) = self.reserve_async(1).await?;
+        Ok(self.create_snowid(ts, seq))
+    }
+
+    /// Generate `count` SnowIDs in strictly increasing order without blocking the runtime
+    ///
+    /// # Panics
+    /// Panics under the same conditions as `generate`.
+    pub async fn generate_batch_async(&self, count: usize) -> Vec<u64> {
+        let mut ids = vec![0; count];
+        if let Err(err) = self.try_generate_into_async(&mut ids).await {
+            panic!("SnowID generation failed: {err}");
+        }
+        ids
+    }
+
+    /// Fill `out` with SnowIDs in strictly increasing order without blocking the runtime
+    ///
+    /// On error, the IDs written before the failure are valid and unique, the rest of
+    /// `out` is left untouched.
+    pub async fn try_generate_into_async(&self, out: &mut [u64]) -> Result<(), SnowIDError> {
+        let mut filled = 0;
+        while filled < out.len() {
+            let wanted = u32::try_from(out.len() - filled).unwrap_or(u32::MAX);
+            let (ts, first_seq, count) = self.reserve_async(wanted).await?;
+            filled += self.fill_reserved(&mut out[filled..], ts, first_seq, count);
+        }
+        Ok(())
+    }
+
+    /// Async counterpart of `reserve`: awaits instead of sleeping the thread
+    async fn reserve_async(&self, wanted: u32) -> Result<(u64, u16, u32), SnowIDError> {
+        loop {
+            match self.try_reserve(wanted)? {
+                Reservation::Claimed {
+                    ts,
+                    first_seq,
+                    count,
+                } => return Ok((ts, first_seq, count)),
+                Reservation::Behind { delta: wait_ms } | Reservation::Exhausted { wait_ms, .. } => {
+                    tokio::time::sleep(Duration::from_millis(wait_ms)).await;
+                }
+                Reservation::Unpersisted => {
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, not(loom)))]
+mod tests {
+    use crate::tests::{EPOCH, ManualClock, tight_config};
+    use crate::*;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_generate_async_shares_state_with_sync() {
+        let generator = SnowID::new(1).unwrap();
+        let first = generator.generate();
+        let second = generator.generate_async().await;
+        let third = generator.generate();
+        assert!(first < second && second < third);
+        assert_eq!(generator.extract.node(second), 1);
+    }
+
+    #[tokio::test]
+    async fn test_generate_batch_async_ordered() {
+        let generator = SnowID::new(1).unwrap();
+        let ids = generator.generate_batch_async(20_000).await;
+        assert_eq!(ids.len(), 20_000);
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_exhaustion_yields_to_runtime() {
+        // 64 slots per millisecond and a clock that only moves when another task advances it
+        let cfg = tight_config().build().unwrap();
+        let clock = Arc::new(ManualClock::new(EPOCH + 1_000));
+        let generator = SnowID::with_clock(1, cfg, Arc::clone(&clock)).unwrap();
+
+        let ticker = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            clock.advance(1);
+        });
+
+        // On a single-threaded runtime this only completes if waiting yields to the ticker
+        let ids = generator.generate_batch_async(100).await;
+        ticker.await.unwrap();
+
+        let timestamps: HashSet<_> = ids
+            .iter()
+            .map(|&id| generator.extract.timestamp(id))
+            .collect();
+        assert_eq!(timestamps, HashSet::from([1_000, 1_001]));
+    }
+
+    #[tokio::test]
+    async fn test_try_generate_async_reports_regression() {
+        let cfg = SnowIDConfig::builder()
+            .clock_regression_policy(ClockRegressionPolicy::Fail)
+            .build()
+            .unwrap();
+        let generator = SnowID::with_clock(1, cfg, ManualClock::new(EPOCH + 1_000)).unwrap();
+        generator.generate_async().await;
+
+        generator.clock().set(EPOCH + 900);
+        assert_eq!(
+            generator.try_generate_async().await,
+            Err(SnowIDError::ClockMovedBackwards { delta: 100 })
+        );
+    }
+}
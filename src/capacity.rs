@@ -0,0 +1,275 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::{SnowIDConfig, SnowIDConfigError};
+
+/// Bits a field needs to hold `count` distinct values
+const fn bits_for(count: u128) -> u8 {
+    if count <= 1 {
+        0
+    } else {
+        (u128::BITS - (count - 1).leading_zeros()) as u8
+    }
+}
+
+/// Node and sequence widths that spare bits are grown towards before extending the timestamp
+const PREFERRED_NODE_BITS: u8 = 10;
+const PREFERRED_SEQUENCE_BITS: u8 = 12;
+
+/// Capacity a layout must provide, used by [`SnowIDConfig::recommend`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityRequirements {
+    nodes: u32,
+    peak_ids_per_second: u64,
+    lifetime: Duration,
+    epoch: Option<u64>,
+    reserve_sign_bit: bool,
+}
+
+impl CapacityRequirements {
+    /// Describe the capacity a layout must provide
+    ///
+    /// # Arguments
+    /// * `nodes` - Number of generators that run concurrently with distinct node IDs
+    /// * `peak_ids_per_second` - Peak rate a single node must sustain
+    /// * `lifetime` - How long from now the timestamp field must last
+    pub const fn new(nodes: u32, peak_ids_per_second: u64, lifetime: Duration) -> Self {
+        Self {
+            nodes,
+            peak_ids_per_second,
+            lifetime,
+            epoch: None,
+            reserve_sign_bit: false,
+        }
+    }
+
+    /// Use a custom epoch in milliseconds since the Unix epoch instead of the default
+    pub const fn epoch(mut self, epoch: u64) -> Self {
+        self.epoch = Some(epoch);
+        self
+    }
+
+    /// Require IDs to stay non-negative as `i64`, leaving 63 bits for the layout
+    pub const fn reserve_sign_bit(mut self, reserve: bool) -> Self {
+        self.reserve_sign_bit = reserve;
+        self
+    }
+}
+
+impl SnowIDConfig {
+    /// Number of distinct node IDs the layout supports
+    #[inline]
+    pub const fn max_nodes(&self) -> u32 {
+        self.max_node_id() as u32 + 1
+    }
+
+    /// IDs a single node can issue per millisecond (fractional with ticks coarser than 1ms)
+    #[inline]
+    pub fn ids_per_millisecond(&self) -> f64 {
+        (self.max_sequence_id() as u64 + 1) as f64 / self.tick_ms() as f64
+    }
+
+    /// IDs the whole cluster can issue per second with every node ID in use
+    #[inline]
+    pub const fn cluster_ids_per_second(&self) -> u64 {
+        let per_tick = self.max_nodes() as u64 * (self.max_sequence_id() as u64 + 1);
+        per_tick * 1000 / self.tick_ms()
+    }
+
+    /// Total span of the timestamp field, from the epoch until it wraps (see `expires_at`)
+    pub const fn lifetime(&self) -> Duration {
+        Duration::from_millis(self.expires_at_millis() - self.epoch())
+    }
+
+    /// Pick a 1ms-tick layout that satisfies `requirements`
+    ///
+    /// Node, sequence and timestamp fields start at the smallest widths that meet the
+    /// requirements. Spare bits grow the sequence, then the node field, up to the default
+    /// 12 and 10 bits, and whatever is left extends the timestamp. When spare bits are short,
+    /// the sequence field gets them first.
+    ///
+    /// # Returns
+    /// * `Result<SnowIDConfig, SnowIDConfigError>` - The recommended configuration, or the
+    ///   first field that cannot be made wide enough: `InvalidNodeBits` for too many nodes,
+    ///   `InvalidSequenceBits` for a rate above 65,536 IDs per millisecond, `InvalidTimestampBits`
+    ///   for a lifetime beyond 63 bits, or `LayoutOverflow` if the fields do not fit together
+    pub fn recommend(requirements: CapacityRequirements) -> Result<Self, SnowIDConfigError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis() as u64);
+        Self::recommend_at(requirements, now)
+    }
+
+    /// `recommend`, with the lifetime counted from `now_unix_ms` instead of the system clock
+    fn recommend_at(
+        requirements: CapacityRequirements,
+        now_unix_ms: u64,
+    ) -> Result<Self, SnowIDConfigError> {
+        let mut builder = Self::builder().reserve_sign_bit(requirements.reserve_sign_bit);
+        if let Some(epoch) = requirements.epoch {
+            builder = builder.epoch(epoch);
+        }
+        let epoch = requirements.epoch.unwrap_or(Self::default().epoch());
+        let max_bits: u8 = if requirements.reserve_sign_bit {
+            63
+        } else {
+            64
+        };
+
+        let end = now_unix_ms as u128 + requirements.lifetime.as_millis();
+        let mut timestamp_bits = bits_for(end.saturating_sub(epoch as u128) + 1).max(1);
+        let mut node_bits = bits_for(requirements.nodes as u128);
+        let mut sequence_bits = bits_for((requirements.peak_ids_per_second as u128).div_ceil(1000));
+
+        if node_bits > 16 {
+            return Err(SnowIDConfigError::InvalidNodeBits { bits: node_bits });
+        }
+        if sequence_bits > 16 {
+            return Err(SnowIDConfigError::InvalidSequenceBits {
+                bits: sequence_bits,
+            });
+        }
+        if timestamp_bits > 63 {
+            return Err(SnowIDConfigError::InvalidTimestampBits {
+                bits: timestamp_bits,
+            });
+        }
+        let used = timestamp_bits + node_bits + sequence_bits;
+        if used > max_bits {
+            return Err(SnowIDConfigError::LayoutOverflow {
+                timestamp_bits,
+                node_bits,
+                sequence_bits,
+                max_bits,
+            });
+        }
+
+        let mut spare = max_bits - used;
+        let grow = PREFERRED_SEQUENCE_BITS
+            .saturating_sub(sequence_bits)
+            .min(spare);
+        sequence_bits += grow;
+        spare -= grow;
+        let grow = PREFERRED_NODE_BITS.saturating_sub(node_bits).min(spare);
+        node_bits += grow;
+        spare -= grow;
+        timestamp_bits = (timestamp_bits + spare).min(63);
+
+        builder
+            .timestamp_bits(timestamp_bits)?
+            .node_bits(node_bits)?
+            .sequence_bits(sequence_bits)?
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YEAR: Duration = Duration::from_secs(365 * 24 * 3600);
+
+    /// Fixed reference time (September 2026) so exact widths do not drift with the clock
+    const NOW: u64 = 1_790_000_000_000;
+
+    fn remaining(cfg: &SnowIDConfig) -> Duration {
+        cfg.expires_at()
+            .unwrap()
+            .duration_since(SystemTime::now())
+            .unwrap()
+    }
+
+    fn remaining_at_now(cfg: &SnowIDConfig) -> Duration {
+        Duration::from_millis(cfg.expires_at_millis() - NOW)
+    }
+
+    #[test]
+    fn test_default_capacity() {
+        let cfg = SnowIDConfig::default();
+        assert_eq!(cfg.max_nodes(), 1024);
+        assert_eq!(cfg.ids_per_millisecond(), 4096.0);
+        assert_eq!(cfg.cluster_ids_per_second(), 1024 * 4096 * 1000);
+        assert_eq!(cfg.lifetime(), Duration::from_millis(1 << 42));
+    }
+
+    #[test]
+    fn test_coarse_tick_capacity() {
+        let cfg = SnowIDConfig::builder()
+            .tick(Duration::from_millis(10))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(cfg.ids_per_millisecond(), 409.6);
+        assert_eq!(cfg.cluster_ids_per_second(), 1024 * 4096 * 100);
+        assert_eq!(cfg.lifetime(), Duration::from_millis(10 << 42));
+    }
+
+    #[test]
+    fn test_bits_for() {
+        assert_eq!(bits_for(0), 0);
+        assert_eq!(bits_for(1), 0);
+        assert_eq!(bits_for(2), 1);
+        assert_eq!(bits_for(1024), 10);
+        assert_eq!(bits_for(1025), 11);
+    }
+
+    #[test]
+    fn test_recommend_typical_cluster() {
+        let cfg =
+            SnowIDConfig::recommend(CapacityRequirements::new(300, 200_000, 50 * YEAR)).unwrap();
+        assert!(cfg.max_nodes() >= 300);
+        assert!(cfg.ids_per_millisecond() >= 200.0);
+        assert!(remaining(&cfg) >= 50 * YEAR);
+        assert!(cfg.total_bits() <= 64);
+    }
+
+    #[test]
+    fn test_recommend_fills_spare_bits() {
+        let requirements = CapacityRequirements::new(300, 200_000, 50 * YEAR);
+        let cfg = SnowIDConfig::recommend_at(requirements, NOW).unwrap();
+        assert!(remaining_at_now(&cfg) >= 50 * YEAR);
+        // Spare bits grow sequence, then node to the defaults, the rest goes to the timestamp
+        assert_eq!(
+            (cfg.timestamp_bits(), cfg.node_bits(), cfg.sequence_bits()),
+            (42, 10, 12)
+        );
+    }
+
+    #[test]
+    fn test_recommend_grows_sequence_before_node() {
+        // 45 timestamp bits + 9 node bits + 8 sequence bits leave 2 spare bits
+        let requirements = CapacityRequirements::new(300, 200_000, 600 * YEAR);
+        let cfg = SnowIDConfig::recommend_at(requirements, NOW).unwrap();
+        assert_eq!(
+            (cfg.timestamp_bits(), cfg.node_bits(), cfg.sequence_bits()),
+            (45, 9, 10)
+        );
+    }
+
+    #[test]
+    fn test_recommend_large_cluster_signed() {
+        let requirements =
+            CapacityRequirements::new(10_000, 500_000, 20 * YEAR).reserve_sign_bit(true);
+        let cfg = SnowIDConfig::recommend_at(requirements, NOW).unwrap();
+        assert!(cfg.reserves_sign_bit());
+        assert_eq!(cfg.total_bits(), 63);
+        assert_eq!(cfg.node_bits(), 14);
+        assert_eq!(cfg.sequence_bits(), 9);
+        assert!(remaining_at_now(&cfg) >= 20 * YEAR);
+    }
+
+    #[test]
+    fn test_recommend_unsatisfiable() {
+        assert!(matches!(
+            SnowIDConfig::recommend(CapacityRequirements::new(100_000, 1_000, YEAR)),
+            Err(SnowIDConfigError::InvalidNodeBits { bits: 17 })
+        ));
+        assert!(matches!(
+            SnowIDConfig::recommend(CapacityRequirements::new(1, 100_000_000, YEAR)),
+            Err(SnowIDConfigError::InvalidSequenceBits { bits: 17 })
+        ));
+        assert!(matches!(
+            SnowIDConfig::recommend(CapacityRequirements::new(65_536, 65_536_000, 100 * YEAR)),
+            Err(SnowIDConfigError::LayoutOverflow { max_bits: 64, .. })
+        ));
+    }
+}
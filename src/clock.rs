@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Source of wall-clock time for SnowID generators
+///
+/// Implementations return the current time as milliseconds since the Unix epoch.
+/// The generator subtracts the configured custom epoch itself, so a clock never
+/// needs to know about `SnowIDConfig`.
+pub trait Clock {
+    /// Current time in milliseconds since the Unix epoch
+    fn now_millis(&self) -> u64;
+}
+
+/// Default clock backed by `SystemTime::now()`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[inline(always)]
+    fn now_millis(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System time before Unix epoch!")
+            .as_millis() as u64
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    #[inline(always)]
+    fn now_millis(&self) -> u64 {
+        (**self).now_millis()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    #[inline(always)]
+    fn now_millis(&self) -> u64 {
+        (**self).now_millis()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedClock(u64);
+
+    impl Clock for FixedClock {
+        fn now_millis(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_system_clock_is_after_default_epoch() {
+        // January 1, 2024 UTC
+        assert!(SystemClock.now_millis() > 1704067200000);
+    }
+
+    fn read<C: Clock>(clock: C) -> u64 {
+        clock.now_millis()
+    }
+
+    #[test]
+    fn test_clock_through_references() {
+        let clock = FixedClock(42);
+        assert_eq!(read(&clock), 42);
+
+        let shared: Arc<dyn Clock> = Arc::new(FixedClock(7));
+        assert_eq!(shared.now_millis(), 7);
+    }
+}
@@ -1,37 +1,114 @@
-use crate::SnowID;
+use crate::datetime::parse_rfc3339;
+use crate::{SnowID, SnowIDError, UnixMillis};
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
 /// Default configuration values
+const DEFAULT_TIMESTAMP_BITS: u8 = SnowID::TIMESTAMP_BITS as u8;
 const DEFAULT_NODE_BITS: u8 = 10;
+const DEFAULT_RESERVE_SIGN_BIT: bool = false;
+const MAX_NODE_BITS: u8 = 16;
+const MAX_SEQUENCE_BITS: u8 = 16;
 const DEFAULT_CUSTOM_EPOCH: u64 = 1704067200000; // January 1, 2024 UTC
+const DEFAULT_TICK_MS: u64 = 1;
+const DEFAULT_OVERFLOW_HORIZON: Duration = Duration::ZERO;
 const DEFAULT_SPIN_ENABLED: bool = true;
 const DEFAULT_SPIN_LOOPS: u32 = 64;
 const DEFAULT_SPIN_YIELD_EVERY: u32 = 16;
+const DEFAULT_FRESH_TIMESTAMPS: bool = true;
+const DEFAULT_CLOCK_REGRESSION_POLICY: ClockRegressionPolicy = ClockRegressionPolicy::Clamp;
+
+/// Behaviour when the clock reads earlier than the last issued timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockRegressionPolicy {
+    /// Keep issuing IDs from the last seen timestamp until the clock catches up
+    Clamp,
+    /// Wait for the clock to catch up if it is behind by at most `tolerance_ms`,
+    /// otherwise fail with `SnowIDError::ClockMovedBackwards`
+    Wait { tolerance_ms: u64 },
+    /// Fail with `SnowIDError::ClockMovedBackwards` as soon as a regression is observed
+    Fail,
+}
 
 /// Configuration for SnowID generator
 /// Copy-optimized with const-evaluable fields
 #[derive(Debug, Clone, Copy)]
 #[repr(C)]
 pub struct SnowIDConfig {
+    timestamp_bits: u8,
     node_bits: u8,
+    datacenter_bits: u8,
+    sequence_bits: u8,
+    reserve_sign_bit: bool,
     custom_epoch: u64,
+    tick_ms: u64,
     timestamp_shift: u8,
     node_shift: u8,
     timestamp_mask: u64,
     node_mask: u16,
+    worker_mask: u16,
     sequence_mask: u16,
     // Throughput tuning
     spin_enabled: bool,
     spin_loops: u32,
     spin_yield_every: u32,
+    // Clock handling
+    fresh_timestamps: bool,
+    clock_regression_policy: ClockRegressionPolicy,
 }
 
 /// Errors related to `SnowIDConfig` builder validation
 #[derive(Debug, Clone, PartialEq, Error)]
 pub enum SnowIDConfigError {
-    /// Provided node bits are out of the supported range [6, 16]
-    #[error("Node bits {bits} must be between 6 and 16")]
+    /// Provided timestamp bits are out of the supported range [1, 63]
+    #[error("Timestamp bits {bits} must be between 1 and 63")]
+    InvalidTimestampBits { bits: u8 },
+    /// Provided node bits are out of the supported range [0, 16]
+    #[error("Node bits {bits} must be between 0 and 16")]
     InvalidNodeBits { bits: u8 },
+    /// Provided (or derived) sequence bits are out of the supported range [0, 16]
+    #[error("Sequence bits {bits} must be between 0 and 16")]
+    InvalidSequenceBits { bits: u8 },
+    /// Timestamp, node and sequence fields do not fit in the available bits
+    #[error(
+        "Layout of {timestamp_bits} timestamp + {node_bits} node + {sequence_bits} sequence bits exceeds {max_bits} bits"
+    )]
+    LayoutOverflow {
+        timestamp_bits: u8,
+        node_bits: u8,
+        sequence_bits: u8,
+        max_bits: u8,
+    },
+    /// Datacenter sub-field is wider than the node field it is carved from
+    #[error("Datacenter bits {bits} must not exceed node bits {node_bits}")]
+    InvalidDatacenterBits { bits: u8, node_bits: u8 },
+    /// Provided tick is zero or not a whole number of milliseconds
+    #[error("Tick {tick:?} must be a non-zero whole number of milliseconds")]
+    InvalidTick { tick: Duration },
+    /// Provided epoch is not a valid time at or after the Unix epoch
+    #[error("Epoch {epoch} is not a valid RFC 3339 time at or after 1970-01-01T00:00:00Z")]
+    InvalidEpoch { epoch: String },
+    /// Custom epoch lies after the current system time
+    #[error("Epoch {epoch} is in the future (current time {now})")]
+    FutureEpoch { epoch: u64, now: u64 },
+    /// The last tick of the timestamp field is not representable in Unix milliseconds
+    #[error(
+        "Timestamp field of {timestamp_bits} bits with {tick_ms}ms ticks from epoch {epoch} exceeds the u64 millisecond range"
+    )]
+    TimestampRangeOverflow {
+        timestamp_bits: u8,
+        tick_ms: u64,
+        epoch: u64,
+    },
+    /// Timestamp field overflows sooner than the required horizon
+    #[error(
+        "Timestamp field overflows in {remaining:?}, within the required horizon of {horizon:?}"
+    )]
+    ExpiresWithinHorizon {
+        remaining: Duration,
+        horizon: Duration,
+    },
 }
 
 impl SnowIDConfig {
@@ -41,20 +118,27 @@ const fn calculate_mask(bits: u8) -> u16 {
         ((1u32 << bits) - 1) as u16
     }
 
-    /// Create new SnowIDConfig with given node bits
-    fn new(node_bits: u8, custom_epoch: u64) -> Self {
-        let sequence_bits = SnowID::TOTAL_NODE_AND_SEQUENCE_BITS - node_bits;
+    /// Create new SnowIDConfig with the given (already validated) bit layout
+    const fn new(timestamp_bits: u8, node_bits: u8, sequence_bits: u8, custom_epoch: u64) -> Self {
         Self {
+            timestamp_bits,
             node_bits,
+            sequence_bits,
+            reserve_sign_bit: DEFAULT_RESERVE_SIGN_BIT,
             custom_epoch,
-            timestamp_shift: SnowID::TOTAL_NODE_AND_SEQUENCE_BITS,
+            tick_ms: DEFAULT_TICK_MS,
+            datacenter_bits: node_bits / 2,
+            timestamp_shift: node_bits + sequence_bits,
             node_shift: sequence_bits,
-            timestamp_mask: (1u64 << SnowID::TIMESTAMP_BITS) - 1,
+            timestamp_mask: (1u64 << timestamp_bits) - 1,
             node_mask: Self::calculate_mask(node_bits),
+            worker_mask: Self::calculate_mask(node_bits - node_bits / 2),
             sequence_mask: Self::calculate_mask(sequence_bits),
             spin_enabled: DEFAULT_SPIN_ENABLED,
             spin_loops: DEFAULT_SPIN_LOOPS,
             spin_yield_every: DEFAULT_SPIN_YIELD_EVERY,
+            fresh_timestamps: DEFAULT_FRESH_TIMESTAMPS,
+            clock_regression_policy: DEFAULT_CLOCK_REGRESSION_POLICY,
         }
     }
 
@@ -69,16 +153,67 @@ pub const fn epoch(&self) -> u64 {
         self.custom_epoch
     }
 
+    /// Get the timestamp resolution: one timestamp unit in the ID equals one tick
+    #[inline(always)]
+    pub const fn tick(&self) -> Duration {
+        Duration::from_millis(self.tick_ms)
+    }
+
+    /// Get the timestamp resolution in milliseconds
+    #[inline(always)]
+    pub const fn tick_ms(&self) -> u64 {
+        self.tick_ms
+    }
+
+    /// Point in time at which the timestamp field wraps and the layout can no longer issue IDs
+    ///
+    /// # Returns
+    /// * `Option<SystemTime>` - Wrap time, or `None` if it lies beyond what `SystemTime`
+    ///   can represent on this platform
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        UNIX_EPOCH.checked_add(Duration::from_millis(self.expires_at_millis()))
+    }
+
+    /// Get timestamp bits configuration
+    #[inline(always)]
+    pub const fn timestamp_bits(&self) -> u8 {
+        self.timestamp_bits
+    }
+
     /// Get node bits configuration
     #[inline(always)]
     pub const fn node_bits(&self) -> u8 {
         self.node_bits
     }
 
-    /// Get sequence bits derived from node bits
+    /// Get the number of high node bits holding the datacenter ID
+    #[inline(always)]
+    pub const fn datacenter_bits(&self) -> u8 {
+        self.datacenter_bits
+    }
+
+    /// Get the number of low node bits holding the worker ID
+    #[inline(always)]
+    pub const fn worker_bits(&self) -> u8 {
+        self.node_bits - self.datacenter_bits
+    }
+
+    /// Get sequence bits configuration
     #[inline(always)]
     pub const fn sequence_bits(&self) -> u8 {
-        SnowID::TOTAL_NODE_AND_SEQUENCE_BITS - self.node_bits
+        self.sequence_bits
+    }
+
+    /// Whether the most significant bit is reserved, keeping IDs non-negative as `i64`
+    #[inline(always)]
+    pub const fn reserves_sign_bit(&self) -> bool {
+        self.reserve_sign_bit
+    }
+
+    /// Total number of bits used by timestamp, node and sequence fields
+    #[inline(always)]
+    pub const fn total_bits(&self) -> u8 {
+        self.timestamp_bits + self.node_bits + self.sequence_bits
     }
 
     /// Get the maximum node ID supported by the current configuration
@@ -87,12 +222,60 @@ pub const fn max_node_id(&self) -> u16 {
         self.node_mask
     }
 
+    /// Get the maximum datacenter ID supported by the current configuration
+    #[inline(always)]
+    pub const fn max_datacenter_id(&self) -> u16 {
+        ((self.node_mask as u32) >> self.worker_bits()) as u16
+    }
+
+    /// Get the maximum worker ID supported by the current configuration
+    #[inline(always)]
+    pub const fn max_worker_id(&self) -> u16 {
+        self.worker_mask
+    }
+
     /// Get the maximum sequence number supported by the current configuration
     #[inline(always)]
     pub const fn max_sequence_id(&self) -> u16 {
         self.sequence_mask
     }
 
+    /// Pack a datacenter and worker ID into a node ID, validating each against its own width
+    ///
+    /// # Returns
+    /// * `Result<u16, SnowIDError>` - Node ID or `InvalidDatacenterId` / `InvalidWorkerId`
+    pub const fn node_id_from_parts(
+        &self,
+        datacenter: u16,
+        worker: u16,
+    ) -> Result<u16, SnowIDError> {
+        let max_datacenter = self.max_datacenter_id();
+        if datacenter > max_datacenter {
+            return Err(SnowIDError::InvalidDatacenterId {
+                datacenter,
+                max: max_datacenter,
+            });
+        }
+        if worker > self.worker_mask {
+            return Err(SnowIDError::InvalidWorkerId {
+                worker,
+                max: self.worker_mask,
+            });
+        }
+        // A zero-width worker field leaves the datacenter as the whole node ID
+        Ok(((datacenter as u32) << self.worker_bits()) as u16 | worker)
+    }
+
+    /// Split a node ID into its datacenter and worker IDs
+    #[inline(always)]
+    pub const fn node_id_parts(&self, node_id: u16) -> (u16, u16) {
+        let node_id = node_id & self.node_mask;
+        (
+            ((node_id as u32) >> self.worker_bits()) as u16,
+            node_id & self.worker_mask,
+        )
+    }
+
     /// Whether micro spin is enabled before sleeping on overflow
     #[inline(always)]
     pub const fn spin_enabled(&self) -> bool {
@@ -111,7 +294,52 @@ pub const fn spin_yield_every(&self) -> u32 {
         self.spin_yield_every
     }
 
+    /// Whether every ID is stamped with the millisecond it was issued in
+    #[inline(always)]
+    pub const fn fresh_timestamps(&self) -> bool {
+        self.fresh_timestamps
+    }
+
+    /// Policy applied when the clock moves backwards
+    #[inline(always)]
+    pub const fn clock_regression_policy(&self) -> ClockRegressionPolicy {
+        self.clock_regression_policy
+    }
+
     // Internal methods used by SnowID and SnowIDExtractor
+    /// Convert milliseconds since epoch to whole ticks (rounding down)
+    #[inline(always)]
+    pub(crate) const fn millis_to_ticks(&self, millis: u64) -> u64 {
+        match self.tick_ms {
+            1 => millis,
+            tick => millis / tick,
+        }
+    }
+
+    /// Convert ticks since epoch to milliseconds since epoch, saturating at `u64::MAX`.
+    /// `build` guarantees every tick of the timestamp field fits.
+    #[inline(always)]
+    pub(crate) const fn ticks_to_millis(&self, ticks: u64) -> u64 {
+        ticks.saturating_mul(self.tick_ms)
+    }
+
+    /// Convert ticks since epoch to milliseconds since epoch, `None` on overflow
+    #[inline(always)]
+    pub(crate) const fn checked_ticks_to_millis(&self, ticks: u64) -> Option<u64> {
+        ticks.checked_mul(self.tick_ms)
+    }
+
+    /// Unix millisecond at which the timestamp field wraps, saturating at `u64::MAX`
+    pub(crate) const fn expires_at_millis(&self) -> u64 {
+        let end =
+            self.custom_epoch as u128 + (self.timestamp_mask as u128 + 1) * self.tick_ms as u128;
+        if end > u64::MAX as u128 {
+            u64::MAX
+        } else {
+            end as u64
+        }
+    }
+
     #[inline(always)]
     pub(crate) const fn timestamp_shift(&self) -> u8 {
         self.timestamp_shift
@@ -140,48 +368,129 @@ pub(crate) const fn sequence_mask(&self) -> u16 {
 
 impl Default for SnowIDConfig {
     fn default() -> Self {
-        Self::new(DEFAULT_NODE_BITS, DEFAULT_CUSTOM_EPOCH)
+        Self::new(
+            DEFAULT_TIMESTAMP_BITS,
+            DEFAULT_NODE_BITS,
+            SnowID::TOTAL_NODE_AND_SEQUENCE_BITS - DEFAULT_NODE_BITS,
+            DEFAULT_CUSTOM_EPOCH,
+        )
     }
 }
 
 /// Builder for SnowIDConfig
 #[derive(Debug)]
 pub struct SnowIDConfigBuilder {
+    timestamp_bits: u8,
     node_bits: u8,
+    datacenter_bits: Option<u8>,
+    sequence_bits: Option<u8>,
+    reserve_sign_bit: bool,
     custom_epoch: u64,
+    tick_ms: u64,
+    overflow_horizon: Duration,
     spin_enabled: bool,
     spin_loops: u32,
     spin_yield_every: u32,
+    fresh_timestamps: bool,
+    clock_regression_policy: ClockRegressionPolicy,
 }
 
 impl SnowIDConfigBuilder {
     /// Create a new SnowIDConfigBuilder with default values
     pub fn new() -> Self {
         Self {
+            timestamp_bits: DEFAULT_TIMESTAMP_BITS,
             node_bits: DEFAULT_NODE_BITS,
+            datacenter_bits: None,
+            sequence_bits: None,
+            reserve_sign_bit: DEFAULT_RESERVE_SIGN_BIT,
             custom_epoch: DEFAULT_CUSTOM_EPOCH,
+            tick_ms: DEFAULT_TICK_MS,
+            overflow_horizon: DEFAULT_OVERFLOW_HORIZON,
             spin_enabled: DEFAULT_SPIN_ENABLED,
             spin_loops: DEFAULT_SPIN_LOOPS,
             spin_yield_every: DEFAULT_SPIN_YIELD_EVERY,
+            fresh_timestamps: DEFAULT_FRESH_TIMESTAMPS,
+            clock_regression_policy: DEFAULT_CLOCK_REGRESSION_POLICY,
         }
     }
 
-    /// Set the number of bits for node ID (6-16) in a fallible way.
-    /// Sequence bits will be automatically set to (22 - node_bits)
+    /// Set the number of bits for the timestamp (1-63) in a fallible way. Defaults to 42.
     ///
     /// # Arguments
-    /// * `bits` - Number of bits for node ID (6-16)
+    /// * `bits` - Number of bits for the timestamp (1-63)
+    ///
+    /// # Returns
+    /// * `Result<Self, SnowIDConfigError>` - Builder instance or validation error
+    pub fn timestamp_bits(mut self, bits: u8) -> Result<Self, SnowIDConfigError> {
+        let true = (1..=63).contains(&bits) else {
+            return Err(SnowIDConfigError::InvalidTimestampBits { bits });
+        };
+        self.timestamp_bits = bits;
+        Ok(self)
+    }
+
+    /// Set the number of bits for node ID (0-16) in a fallible way.
+    /// Unless set explicitly, sequence bits take all bits left after timestamp and node,
+    /// i.e. (22 - node_bits) with the default 42-bit timestamp.
+    ///
+    /// # Arguments
+    /// * `bits` - Number of bits for node ID (0-16)
     ///
     /// # Returns
     /// * `Result<Self, SnowIDConfigError>` - Builder instance or validation error
     pub fn node_bits(mut self, bits: u8) -> Result<Self, SnowIDConfigError> {
-        let true = (6..=16).contains(&bits) else {
+        let true = (0..=MAX_NODE_BITS).contains(&bits) else {
             return Err(SnowIDConfigError::InvalidNodeBits { bits });
         };
         self.node_bits = bits;
         Ok(self)
     }
 
+    /// Split the node field into a datacenter ID (the high `bits`) and a worker ID
+    /// (the remaining low bits) in a fallible way. Defaults to half of the node bits,
+    /// rounded down, which gives the classic 5/5 split for 10 node bits.
+    /// Checked against the node bits when the configuration is built.
+    ///
+    /// # Arguments
+    /// * `bits` - Number of high node bits for the datacenter ID (0-16)
+    ///
+    /// # Returns
+    /// * `Result<Self, SnowIDConfigError>` - Builder instance or validation error
+    pub fn datacenter_bits(mut self, bits: u8) -> Result<Self, SnowIDConfigError> {
+        let true = (0..=MAX_NODE_BITS).contains(&bits) else {
+            return Err(SnowIDConfigError::InvalidDatacenterBits {
+                bits,
+                node_bits: MAX_NODE_BITS,
+            });
+        };
+        self.datacenter_bits = Some(bits);
+        Ok(self)
+    }
+
+    /// Set the number of bits for the sequence (0-16) in a fallible way.
+    /// When not set, the sequence takes all bits left after timestamp and node.
+    ///
+    /// # Arguments
+    /// * `bits` - Number of bits for the sequence (0-16)
+    ///
+    /// # Returns
+    /// * `Result<Self, SnowIDConfigError>` - Builder instance or validation error
+    pub fn sequence_bits(mut self, bits: u8) -> Result<Self, SnowIDConfigError> {
+        let true = (0..=MAX_SEQUENCE_BITS).contains(&bits) else {
+            return Err(SnowIDConfigError::InvalidSequenceBits { bits });
+        };
+        self.sequence_bits = Some(bits);
+        Ok(self)
+    }
+
+    /// Reserve the most significant bit so IDs always fit a non-negative `i64`
+    /// (Java `long`, Postgres `bigint`). The layout must then fit in 63 bits.
+    pub const fn reserve_sign_bit(mut self, reserve: bool) -> Self {
+        self.reserve_sign_bit = reserve;
+        self
+    }
+
     /// Set a custom epoch timestamp in milliseconds
     ///
     /// # Arguments
@@ -191,6 +500,63 @@ pub const fn epoch(mut self, epoch: u64) -> Self {
         self
     }
 
+    /// Set the custom epoch from a date-time, e.g. `SystemTime` or `chrono::DateTime<Utc>`
+    /// with the `chrono` feature, in a fallible way
+    ///
+    /// # Returns
+    /// * `Result<Self, SnowIDConfigError>` - Builder instance or `InvalidEpoch` for times before 1970
+    pub fn epoch_at<T: UnixMillis + fmt::Debug>(self, at: T) -> Result<Self, SnowIDConfigError> {
+        match at.to_unix_millis() {
+            Some(epoch) => Ok(self.epoch(epoch)),
+            None => Err(SnowIDConfigError::InvalidEpoch {
+                epoch: format!("{at:?}"),
+            }),
+        }
+    }
+
+    /// Set the custom epoch from an RFC 3339 string such as `2024-01-01T00:00:00Z`
+    /// in a fallible way
+    ///
+    /// # Returns
+    /// * `Result<Self, SnowIDConfigError>` - Builder instance or `InvalidEpoch`
+    pub fn epoch_rfc3339(self, epoch: &str) -> Result<Self, SnowIDConfigError> {
+        match parse_rfc3339(epoch) {
+            Some(epoch) => Ok(self.epoch(epoch)),
+            None => Err(SnowIDConfigError::InvalidEpoch {
+                epoch: epoch.to_owned(),
+            }),
+        }
+    }
+
+    /// Set the timestamp resolution (e.g. 1ms, 10ms or 1s) in a fallible way.
+    /// Coarser ticks extend the lifetime of the timestamp field at the cost of fewer
+    /// IDs per unit of time. Defaults to 1ms.
+    ///
+    /// # Arguments
+    /// * `tick` - Duration of one timestamp unit; must be a non-zero whole number of milliseconds
+    ///
+    /// # Returns
+    /// * `Result<Self, SnowIDConfigError>` - Builder instance or validation error
+    pub fn tick(mut self, tick: Duration) -> Result<Self, SnowIDConfigError> {
+        let millis = tick.as_millis();
+        if millis == 0
+            || !tick.subsec_nanos().is_multiple_of(1_000_000)
+            || millis > u64::MAX as u128
+        {
+            return Err(SnowIDConfigError::InvalidTick { tick });
+        }
+        self.tick_ms = millis as u64;
+        Ok(self)
+    }
+
+    /// Require the timestamp field to last at least `horizon` from now. `build()` fails with
+    /// `ExpiresWithinHorizon` otherwise. Defaults to zero, which only rejects layouts
+    /// that have already overflowed.
+    pub const fn overflow_horizon(mut self, horizon: Duration) -> Self {
+        self.overflow_horizon = horizon;
+        self
+    }
+
     /// Enable or disable micro spin before sleep on overflow
     pub const fn enable_spin(mut self, enable: bool) -> Self {
         self.spin_enabled = enable;
@@ -210,16 +576,100 @@ pub const fn spin_yield_every(mut self, n: u32) -> Self {
         self
     }
 
+    /// Read the clock on every generation so each ID carries the millisecond it was issued in.
+    /// When disabled, the fast path reuses the last timestamp until the sequence is exhausted,
+    /// which saves a clock read per ID but can stamp IDs with a stale millisecond.
+    /// Enabled by default.
+    pub const fn fresh_timestamps(mut self, enable: bool) -> Self {
+        self.fresh_timestamps = enable;
+        self
+    }
+
+    /// Set how the generator reacts when the clock moves backwards.
+    /// Defaults to `ClockRegressionPolicy::Clamp`.
+    pub const fn clock_regression_policy(mut self, policy: ClockRegressionPolicy) -> Self {
+        self.clock_regression_policy = policy;
+        self
+    }
+
     /// Build the final SnowIDConfig
     ///
     /// # Returns
-    /// * `SnowIDConfig` - The configured SnowIDConfig instance
-    pub fn build(self) -> SnowIDConfig {
-        let mut cfg = SnowIDConfig::new(self.node_bits, self.custom_epoch);
+    /// * `Result<SnowIDConfig, SnowIDConfigError>` - The configured SnowIDConfig instance or
+    ///   an error if the fields do not fit in 64 (or 63) bits, the end of the timestamp field
+    ///   is not representable in u64 Unix milliseconds, the epoch lies in the future, or the
+    ///   timestamp field overflows within the configured horizon
+    pub fn build(self) -> Result<SnowIDConfig, SnowIDConfigError> {
+        let max_bits: u8 = if self.reserve_sign_bit { 63 } else { 64 };
+        let used_bits = self.timestamp_bits + self.node_bits;
+        let sequence_bits = match self.sequence_bits {
+            Some(bits) => bits,
+            None => max_bits.saturating_sub(used_bits),
+        };
+        if used_bits + sequence_bits > max_bits {
+            return Err(SnowIDConfigError::LayoutOverflow {
+                timestamp_bits: self.timestamp_bits,
+                node_bits: self.node_bits,
+                sequence_bits,
+                max_bits,
+            });
+        }
+        if sequence_bits > MAX_SEQUENCE_BITS {
+            return Err(SnowIDConfigError::InvalidSequenceBits {
+                bits: sequence_bits,
+            });
+        }
+        let datacenter_bits = self.datacenter_bits.unwrap_or(self.node_bits / 2);
+        if datacenter_bits > self.node_bits {
+            return Err(SnowIDConfigError::InvalidDatacenterBits {
+                bits: datacenter_bits,
+                node_bits: self.node_bits,
+            });
+        }
+
+        let mut cfg = SnowIDConfig::new(
+            self.timestamp_bits,
+            self.node_bits,
+            sequence_bits,
+            self.custom_epoch,
+        );
+        cfg.datacenter_bits = datacenter_bits;
+        cfg.worker_mask = SnowIDConfig::calculate_mask(self.node_bits - datacenter_bits);
+        cfg.reserve_sign_bit = self.reserve_sign_bit;
+        cfg.tick_ms = self.tick_ms;
         cfg.spin_enabled = self.spin_enabled;
         cfg.spin_loops = self.spin_loops;
         cfg.spin_yield_every = self.spin_yield_every;
-        cfg
+        cfg.fresh_timestamps = self.fresh_timestamps;
+        cfg.clock_regression_policy = self.clock_regression_policy;
+
+        let end =
+            self.custom_epoch as u128 + (cfg.timestamp_mask() as u128 + 1) * self.tick_ms as u128;
+        if end > u64::MAX as u128 {
+            return Err(SnowIDConfigError::TimestampRangeOverflow {
+                timestamp_bits: self.timestamp_bits,
+                tick_ms: self.tick_ms,
+                epoch: self.custom_epoch,
+            });
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis() as u64);
+        if self.custom_epoch > now {
+            return Err(SnowIDConfigError::FutureEpoch {
+                epoch: self.custom_epoch,
+                now,
+            });
+        }
+        let remaining = Duration::from_millis(cfg.expires_at_millis().saturating_sub(now));
+        if remaining <= self.overflow_horizon {
+            return Err(SnowIDConfigError::ExpiresWithinHorizon {
+                remaining,
+                horizon: self.overflow_horizon,
+            });
+        }
+        Ok(cfg)
     }
 }
 
@@ -238,9 +688,13 @@ mod node_bits_validation {
 
         #[test]
         fn test_valid_node_bits() {
-            // Test all valid node bits from 6 to 16
+            // Node bits from 6 to 16 leave a sequence field of at most 16 bits
             for bits in 6..=16 {
-                let config = SnowIDConfig::builder().node_bits(bits).unwrap().build();
+                let config = SnowIDConfig::builder()
+                    .node_bits(bits)
+                    .unwrap()
+                    .build()
+                    .unwrap();
                 assert_eq!(config.node_bits(), bits);
                 assert_eq!(
                     config.sequence_bits(),
@@ -252,14 +706,126 @@ fn test_valid_node_bits() {
 
         #[test]
         fn test_node_bits_ok() {
-            let cfg = SnowIDConfig::builder().node_bits(12).unwrap().build();
+            let cfg = SnowIDConfig::builder()
+                .node_bits(12)
+                .unwrap()
+                .build()
+                .unwrap();
             assert_eq!(cfg.node_bits(), 12);
         }
 
         #[test]
         fn test_node_bits_err() {
-            let err = SnowIDConfig::builder().node_bits(5).unwrap_err();
-            assert_eq!(err, SnowIDConfigError::InvalidNodeBits { bits: 5 });
+            let err = SnowIDConfig::builder().node_bits(17).unwrap_err();
+            assert_eq!(err, SnowIDConfigError::InvalidNodeBits { bits: 17 });
+        }
+
+        #[test]
+        fn test_small_node_bits_need_explicit_sequence() {
+            // 42 + 5 leaves 17 bits, more than the sequence field supports
+            let err = SnowIDConfig::builder()
+                .node_bits(5)
+                .unwrap()
+                .build()
+                .unwrap_err();
+            assert_eq!(err, SnowIDConfigError::InvalidSequenceBits { bits: 17 });
+
+            let cfg = SnowIDConfig::builder()
+                .node_bits(0)
+                .unwrap()
+                .sequence_bits(16)
+                .unwrap()
+                .build()
+                .unwrap();
+            assert_eq!(cfg.max_node_id(), 0);
+            assert_eq!(cfg.max_sequence_id(), 0xFFFF);
+        }
+    }
+
+    mod layout_validation {
+        use super::*;
+
+        #[test]
+        fn test_instagram_layout() {
+            let cfg = SnowIDConfig::builder()
+                .timestamp_bits(41)
+                .unwrap()
+                .node_bits(13)
+                .unwrap()
+                .sequence_bits(10)
+                .unwrap()
+                .build()
+                .unwrap();
+            assert_eq!(cfg.timestamp_bits(), 41);
+            assert_eq!(cfg.total_bits(), 64);
+            assert_eq!(cfg.timestamp_shift(), 23);
+            assert_eq!(cfg.node_shift(), 10);
+            assert_eq!(cfg.timestamp_mask(), (1u64 << 41) - 1);
+            assert_eq!(cfg.max_node_id(), 8191);
+            assert_eq!(cfg.max_sequence_id(), 1023);
+        }
+
+        #[test]
+        fn test_reserved_sign_bit_derives_sequence() {
+            let cfg = SnowIDConfig::builder()
+                .reserve_sign_bit(true)
+                .build()
+                .unwrap();
+            assert!(cfg.reserves_sign_bit());
+            assert_eq!(cfg.timestamp_bits(), 42);
+            assert_eq!(cfg.node_bits(), 10);
+            assert_eq!(cfg.sequence_bits(), 11);
+            assert_eq!(cfg.total_bits(), 63);
+        }
+
+        #[test]
+        fn test_layout_too_wide() {
+            let err = SnowIDConfig::builder()
+                .reserve_sign_bit(true)
+                .timestamp_bits(41)
+                .unwrap()
+                .node_bits(13)
+                .unwrap()
+                .sequence_bits(10)
+                .unwrap()
+                .build()
+                .unwrap_err();
+            assert_eq!(
+                err,
+                SnowIDConfigError::LayoutOverflow {
+                    timestamp_bits: 41,
+                    node_bits: 13,
+                    sequence_bits: 10,
+                    max_bits: 63,
+                }
+            );
+            assert_eq!(
+                err.to_string(),
+                "Layout of 41 timestamp + 13 node + 10 sequence bits exceeds 63 bits"
+            );
+
+            let err = SnowIDConfig::builder()
+                .timestamp_bits(60)
+                .unwrap()
+                .build()
+                .unwrap_err();
+            assert!(matches!(err, SnowIDConfigError::LayoutOverflow { .. }));
+        }
+
+        #[test]
+        fn test_field_width_errors() {
+            assert_eq!(
+                SnowIDConfig::builder().timestamp_bits(0).unwrap_err(),
+                SnowIDConfigError::InvalidTimestampBits { bits: 0 }
+            );
+            assert_eq!(
+                SnowIDConfig::builder().timestamp_bits(64).unwrap_err(),
+                SnowIDConfigError::InvalidTimestampBits { bits: 64 }
+            );
+            assert_eq!(
+                SnowIDConfig::builder().sequence_bits(17).unwrap_err(),
+                SnowIDConfigError::InvalidSequenceBits { bits: 17 }
+            );
         }
     }
 
@@ -269,7 +835,8 @@ fn test_custom_config() {
             .node_bits(12)
             .unwrap()
             .epoch(1640995200000) // 2022-01-01
-            .build();
+            .build()
+            .unwrap();
 
         assert_eq!(config.node_bits(), 12);
         assert_eq!(config.sequence_bits(), 10); // 22 - 12
@@ -288,6 +855,340 @@ fn test_default_config() {
         assert_eq!(config.spin_enabled(), DEFAULT_SPIN_ENABLED);
         assert_eq!(config.spin_loops(), DEFAULT_SPIN_LOOPS);
         assert_eq!(config.spin_yield_every(), DEFAULT_SPIN_YIELD_EVERY);
+        assert_eq!(config.fresh_timestamps(), DEFAULT_FRESH_TIMESTAMPS);
+        assert_eq!(
+            config.clock_regression_policy(),
+            ClockRegressionPolicy::Clamp
+        );
+    }
+
+    mod datacenter_split {
+        use super::*;
+
+        #[test]
+        fn test_default_split() {
+            let cfg = SnowIDConfig::default();
+            assert_eq!(cfg.datacenter_bits(), 5);
+            assert_eq!(cfg.worker_bits(), 5);
+            assert_eq!(cfg.max_datacenter_id(), 31);
+            assert_eq!(cfg.max_worker_id(), 31);
+
+            let built = SnowIDConfig::builder()
+                .node_bits(13)
+                .unwrap()
+                .sequence_bits(9)
+                .unwrap()
+                .build()
+                .unwrap();
+            assert_eq!(built.datacenter_bits(), 6);
+            assert_eq!(built.worker_bits(), 7);
+        }
+
+        #[test]
+        fn test_parts_roundtrip() {
+            let cfg = SnowIDConfig::builder()
+                .node_bits(12)
+                .unwrap()
+                .datacenter_bits(3)
+                .unwrap()
+                .build()
+                .unwrap();
+            assert_eq!(cfg.max_datacenter_id(), 7);
+            assert_eq!(cfg.max_worker_id(), 511);
+
+            let node = cfg.node_id_from_parts(5, 300).unwrap();
+            assert_eq!(node, (5 << 9) | 300);
+            assert_eq!(cfg.node_id_parts(node), (5, 300));
+
+            assert_eq!(
+                cfg.node_id_from_parts(8, 0).unwrap_err(),
+                SnowIDError::InvalidDatacenterId {
+                    datacenter: 8,
+                    max: 7
+                }
+            );
+            assert_eq!(
+                cfg.node_id_from_parts(0, 512).unwrap_err(),
+                SnowIDError::InvalidWorkerId {
+                    worker: 512,
+                    max: 511
+                }
+            );
+        }
+
+        #[test]
+        fn test_full_width_parts() {
+            let all_worker = SnowIDConfig::builder()
+                .node_bits(16)
+                .unwrap()
+                .datacenter_bits(0)
+                .unwrap()
+                .build()
+                .unwrap();
+            assert_eq!(all_worker.max_datacenter_id(), 0);
+            assert_eq!(all_worker.node_id_parts(u16::MAX), (0, u16::MAX));
+
+            let all_datacenter = SnowIDConfig::builder()
+                .node_bits(16)
+                .unwrap()
+                .datacenter_bits(16)
+                .unwrap()
+                .build()
+                .unwrap();
+            assert_eq!(all_datacenter.max_worker_id(), 0);
+            assert_eq!(all_datacenter.node_id_from_parts(u16::MAX, 0), Ok(u16::MAX));
+            assert_eq!(all_datacenter.node_id_parts(u16::MAX), (u16::MAX, 0));
+        }
+
+        #[test]
+        fn test_datacenter_bits_out_of_range() {
+            let err = SnowIDConfig::builder().datacenter_bits(17).unwrap_err();
+            assert_eq!(
+                err,
+                SnowIDConfigError::InvalidDatacenterBits {
+                    bits: 17,
+                    node_bits: 16
+                }
+            );
+        }
+
+        #[test]
+        fn test_datacenter_wider_than_node() {
+            let err = SnowIDConfig::builder()
+                .node_bits(8)
+                .unwrap()
+                .datacenter_bits(9)
+                .unwrap()
+                .build()
+                .unwrap_err();
+            assert_eq!(
+                err,
+                SnowIDConfigError::InvalidDatacenterBits {
+                    bits: 9,
+                    node_bits: 8
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_tick_builder() {
+        let cfg = SnowIDConfig::builder()
+            .tick(Duration::from_millis(10))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(cfg.tick(), Duration::from_millis(10));
+        assert_eq!(cfg.tick_ms(), 10);
+        assert_eq!(cfg.millis_to_ticks(1_234), 123);
+        assert_eq!(cfg.ticks_to_millis(123), 1_230);
+
+        assert_eq!(SnowIDConfig::default().tick_ms(), DEFAULT_TICK_MS);
+    }
+
+    mod timestamp_range {
+        use super::*;
+        use crate::{IdProblem, SnowIDExtractor, ValidationRules};
+
+        #[test]
+        fn test_rejects_layout_beyond_u64_millis() {
+            let err = SnowIDConfig::builder()
+                .timestamp_bits(60)
+                .unwrap()
+                .node_bits(0)
+                .unwrap()
+                .sequence_bits(4)
+                .unwrap()
+                .tick(Duration::from_secs(1))
+                .unwrap()
+                .build()
+                .unwrap_err();
+            assert_eq!(
+                err,
+                SnowIDConfigError::TimestampRangeOverflow {
+                    timestamp_bits: 60,
+                    tick_ms: 1000,
+                    epoch: DEFAULT_CUSTOM_EPOCH
+                }
+            );
+
+            // The widest 1s-tick field that still fits
+            let cfg = SnowIDConfig::builder()
+                .timestamp_bits(53)
+                .unwrap()
+                .tick(Duration::from_secs(1))
+                .unwrap()
+                .build()
+                .unwrap();
+            let extract = SnowIDExtractor::new(cfg);
+            assert!(extract.unix_millis(u64::MAX) < cfg.expires_at_millis());
+        }
+
+        #[test]
+        fn test_validate_reports_unrepresentable_timestamp() {
+            // Bypass `build` to get a layout whose last ticks overflow
+            let mut cfg = SnowIDConfig::builder()
+                .timestamp_bits(60)
+                .unwrap()
+                .node_bits(0)
+                .unwrap()
+                .sequence_bits(4)
+                .unwrap()
+                .build()
+                .unwrap();
+            cfg.tick_ms = 1000;
+            let extract = SnowIDExtractor::new(cfg);
+
+            assert_eq!(extract.unix_millis(u64::MAX), u64::MAX);
+            assert_eq!(extract.system_time(u64::MAX), None);
+            let err = extract
+                .validate(u64::MAX, &ValidationRules::new(), crate::SystemClock)
+                .unwrap_err();
+            assert_eq!(
+                err.problems,
+                vec![IdProblem::TimestampOutOfRange {
+                    ticks: (1 << 60) - 1
+                }]
+            );
+        }
+    }
+
+    mod epoch_validation {
+        use super::*;
+
+        #[test]
+        fn test_epoch_from_datetime() {
+            let cfg = SnowIDConfig::builder()
+                .epoch_rfc3339("2020-01-01T00:00:00Z")
+                .unwrap()
+                .build()
+                .unwrap();
+            assert_eq!(cfg.epoch(), 1577836800000);
+
+            let at = UNIX_EPOCH + Duration::from_millis(1577836800000);
+            let cfg = SnowIDConfig::builder()
+                .epoch_at(at)
+                .unwrap()
+                .build()
+                .unwrap();
+            assert_eq!(cfg.epoch(), 1577836800000);
+        }
+
+        #[test]
+        fn test_invalid_epoch() {
+            let err = SnowIDConfig::builder()
+                .epoch_rfc3339("2020-01-01")
+                .unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "Epoch 2020-01-01 is not a valid RFC 3339 time at or after 1970-01-01T00:00:00Z"
+            );
+
+            let before = UNIX_EPOCH - Duration::from_secs(1);
+            assert!(matches!(
+                SnowIDConfig::builder().epoch_at(before),
+                Err(SnowIDConfigError::InvalidEpoch { .. })
+            ));
+        }
+
+        #[test]
+        fn test_future_epoch_rejected() {
+            let epoch = u64::MAX / 2;
+            match SnowIDConfig::builder().epoch(epoch).build() {
+                Err(SnowIDConfigError::FutureEpoch { epoch: e, now }) => {
+                    assert_eq!(e, epoch);
+                    assert!(now < epoch);
+                }
+                other => panic!("expected FutureEpoch, got {other:?}"),
+            }
+        }
+
+        #[test]
+        fn test_expires_at_default_layout() {
+            let cfg = SnowIDConfig::default();
+            let expected = UNIX_EPOCH + Duration::from_millis(DEFAULT_CUSTOM_EPOCH + (1u64 << 42));
+            assert_eq!(cfg.expires_at(), Some(expected));
+
+            let coarse = SnowIDConfig::builder()
+                .tick(Duration::from_millis(10))
+                .unwrap()
+                .build()
+                .unwrap();
+            assert_eq!(
+                coarse.expires_at(),
+                Some(UNIX_EPOCH + Duration::from_millis(DEFAULT_CUSTOM_EPOCH + 10 * (1u64 << 42)))
+            );
+        }
+
+        #[test]
+        fn test_overflow_horizon() {
+            // 32 bits of milliseconds last under 50 days
+            let short = || {
+                SnowIDConfig::builder()
+                    .timestamp_bits(32)
+                    .unwrap()
+                    .sequence_bits(12)
+                    .unwrap()
+            };
+            match short().build() {
+                Err(SnowIDConfigError::ExpiresWithinHorizon { remaining, horizon }) => {
+                    assert_eq!(remaining, Duration::ZERO);
+                    assert_eq!(horizon, Duration::ZERO);
+                }
+                other => panic!("expected ExpiresWithinHorizon, got {other:?}"),
+            }
+
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+            let fresh_epoch = now.as_millis() as u64 - 1_000;
+            let cfg = short().epoch(fresh_epoch).build().unwrap();
+            assert!(cfg.expires_at().unwrap() > SystemTime::now());
+
+            let year = Duration::from_secs(365 * 24 * 3600);
+            let err = short()
+                .epoch(fresh_epoch)
+                .overflow_horizon(year)
+                .build()
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                SnowIDConfigError::ExpiresWithinHorizon { horizon, .. } if horizon == year
+            ));
+
+            // The default layout lasts until 2163
+            SnowIDConfig::builder()
+                .overflow_horizon(year * 100)
+                .build()
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_tick_builder_err() {
+        for tick in [Duration::ZERO, Duration::from_micros(1_500)] {
+            let err = SnowIDConfig::builder().tick(tick).unwrap_err();
+            assert_eq!(err, SnowIDConfigError::InvalidTick { tick });
+        }
+    }
+
+    #[test]
+    fn test_fresh_timestamps_builder() {
+        let cfg = SnowIDConfig::builder()
+            .fresh_timestamps(false)
+            .build()
+            .unwrap();
+        assert!(!cfg.fresh_timestamps());
+    }
+
+    #[test]
+    fn test_clock_regression_policy_builder() {
+        let cfg = SnowIDConfig::builder()
+            .clock_regression_policy(ClockRegressionPolicy::Wait { tolerance_ms: 50 })
+            .build()
+            .unwrap();
+        assert_eq!(
+            cfg.clock_regression_policy(),
+            ClockRegressionPolicy::Wait { tolerance_ms: 50 }
+        );
     }
 
     // Panicking builder has been removed; validation is error-based.
@@ -295,6 +1196,9 @@ fn test_default_config() {
     #[test]
     fn test_bit_config() {
         let config = SnowIDConfig::default();
+        assert_eq!(config.timestamp_bits(), 42);
+        assert_eq!(config.total_bits(), 64);
+        assert!(!config.reserves_sign_bit());
         assert_eq!(config.node_shift(), 12);
         assert_eq!(config.timestamp_shift(), 22);
         assert_eq!(config.sequence_mask(), 0xFFF);
@@ -310,7 +1214,8 @@ fn test_spin_tuning_builder() {
             .enable_spin(false)
             .spin_loops(0)
             .spin_yield_every(0)
-            .build();
+            .build()
+            .unwrap();
         assert!(!cfg.spin_enabled());
         assert_eq!(cfg.spin_loops(), 0);
         assert_eq!(cfg.spin_yield_every(), 0);
@@ -319,7 +1224,8 @@ fn test_spin_tuning_builder() {
             .enable_spin(true)
             .spin_loops(128)
             .spin_yield_every(8)
-            .build();
+            .build()
+            .unwrap();
         assert!(cfg2.spin_enabled());
         assert_eq!(cfg2.spin_loops(), 128);
         assert_eq!(cfg2.spin_yield_every(), 8);
@@ -0,0 +1,243 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Date-time types that convert to and from milliseconds since the Unix epoch
+///
+/// Implemented for `SystemTime`, and behind the `chrono`, `time` and `jiff` features for
+/// `chrono::DateTime<Utc>`, `time::OffsetDateTime` and `jiff::Timestamp`.
+pub trait UnixMillis: Sized {
+    /// Build a value from milliseconds since the Unix epoch, `None` if out of range for the type
+    fn from_unix_millis(millis: u64) -> Option<Self>;
+
+    /// Milliseconds since the Unix epoch (sub-millisecond precision is truncated),
+    /// `None` for times before 1970
+    fn to_unix_millis(&self) -> Option<u64>;
+}
+
+impl UnixMillis for SystemTime {
+    fn from_unix_millis(millis: u64) -> Option<Self> {
+        UNIX_EPOCH.checked_add(Duration::from_millis(millis))
+    }
+
+    fn to_unix_millis(&self) -> Option<u64> {
+        let since = self.duration_since(UNIX_EPOCH).ok()?;
+        u64::try_from(since.as_millis()).ok()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl UnixMillis for chrono::DateTime<chrono::Utc> {
+    fn from_unix_millis(millis: u64) -> Option<Self> {
+        Self::from_timestamp_millis(i64::try_from(millis).ok()?)
+    }
+
+    fn to_unix_millis(&self) -> Option<u64> {
+        u64::try_from(self.timestamp_millis()).ok()
+    }
+}
+
+#[cfg(feature = "time")]
+impl UnixMillis for time::OffsetDateTime {
+    fn from_unix_millis(millis: u64) -> Option<Self> {
+        Self::from_unix_timestamp_nanos(millis as i128 * 1_000_000).ok()
+    }
+
+    fn to_unix_millis(&self) -> Option<u64> {
+        u64::try_from(self.unix_timestamp_nanos().div_euclid(1_000_000)).ok()
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl UnixMillis for jiff::Timestamp {
+    fn from_unix_millis(millis: u64) -> Option<Self> {
+        Self::from_millisecond(i64::try_from(millis).ok()?).ok()
+    }
+
+    fn to_unix_millis(&self) -> Option<u64> {
+        u64::try_from(self.as_millisecond()).ok()
+    }
+}
+
+/// Parse an RFC 3339 timestamp (e.g. `2024-01-01T00:00:00Z` or `2024-01-01T08:00:00.250+08:00`)
+/// into milliseconds since the Unix epoch. Fractions beyond milliseconds are truncated;
+/// times before 1970 and leap seconds are rejected.
+pub(crate) fn parse_rfc3339(input: &str) -> Option<u64> {
+    let bytes = input.as_bytes();
+    let number = |start: usize, len: usize| -> Option<i64> {
+        bytes
+            .get(start..start + len)?
+            .iter()
+            .try_fold(0i64, |acc, &c| {
+                c.is_ascii_digit().then(|| acc * 10 + (c - b'0') as i64)
+            })
+    };
+    let separator = |at: usize, allowed: &[u8]| bytes.get(at).is_some_and(|c| allowed.contains(c));
+
+    let year = number(0, 4)?;
+    let month = number(5, 2)?;
+    let day = number(8, 2)?;
+    let hour = number(11, 2)?;
+    let minute = number(14, 2)?;
+    let second = number(17, 2)?;
+    let separators_ok = separator(4, b"-")
+        && separator(7, b"-")
+        && separator(10, b"Tt ")
+        && separator(13, b":")
+        && separator(16, b":");
+    if !separators_ok
+        || !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return None;
+    }
+
+    let mut rest = &bytes[19..];
+    let mut millis = 0;
+    if let [b'.', fraction @ ..] = rest {
+        let digits = fraction.iter().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return None;
+        }
+        for position in 0..3 {
+            let digit = fraction.get(position).filter(|_| position < digits);
+            millis = millis * 10 + digit.map_or(0, |&c| (c - b'0') as i64);
+        }
+        rest = &fraction[digits..];
+    }
+
+    let offset_secs = match rest {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2] => {
+            let digits = [*h1, *h2, *m1, *m2];
+            if !digits.iter().all(u8::is_ascii_digit) {
+                return None;
+            }
+            let [h1, h2, m1, m2] = digits.map(|c| (c - b'0') as i64);
+            let (hours, minutes) = (h1 * 10 + h2, m1 * 10 + m2);
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = hours * 3600 + minutes * 60;
+            if *sign == b'+' { offset } else { -offset }
+        }
+        _ => return None,
+    };
+
+    let secs = days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second
+        - offset_secs;
+    u64::try_from(secs * 1000 + millis).ok()
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date (`year` in 0..=9999)
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // Shift the year to start in March so the leap day is the last day of the year
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rfc3339() {
+        assert_eq!(parse_rfc3339("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_rfc3339("2024-01-01T00:00:00Z"), Some(1704067200000));
+        assert_eq!(parse_rfc3339("2020-01-01t00:00:00z"), Some(1577836800000));
+        assert_eq!(parse_rfc3339("2024-02-29T12:30:45.5Z"), Some(1709209845500));
+        assert_eq!(
+            parse_rfc3339("2024-01-01T08:00:00.123456+08:00"),
+            Some(1704067200123)
+        );
+        assert_eq!(
+            parse_rfc3339("2023-12-31T19:00:00-05:00"),
+            Some(1704067200000)
+        );
+        assert_eq!(parse_rfc3339("2024-01-01 00:00:00Z"), Some(1704067200000));
+    }
+
+    #[test]
+    fn test_parse_rfc3339_rejects_invalid() {
+        for input in [
+            "",
+            "2024-01-01",
+            "2024-01-01T00:00:00",
+            "2024-13-01T00:00:00Z",
+            "2023-02-29T00:00:00Z",
+            "2024-01-01T24:00:00Z",
+            "2024-01-01T00:00:60Z",
+            "2024-01-01T00:00:00.Z",
+            "2024-01-01T00:00:00+0800",
+            "2024-01-01T00:00:00+24:00",
+            "2024-01-01T00:00:00Z ",
+            "1969-12-31T23:59:59Z",
+            "1970-01-01T00:00:00+00:01",
+            "２024-01-01T00:00:00Z",
+        ] {
+            assert_eq!(parse_rfc3339(input), None, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_system_time_roundtrip() {
+        let time = SystemTime::from_unix_millis(1704067200123).unwrap();
+        assert_eq!(time, UNIX_EPOCH + Duration::from_millis(1704067200123));
+        assert_eq!(time.to_unix_millis(), Some(1704067200123));
+        assert_eq!((UNIX_EPOCH - Duration::from_secs(1)).to_unix_millis(), None);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_roundtrip() {
+        use chrono::{DateTime, TimeZone, Utc};
+
+        let time = DateTime::<Utc>::from_unix_millis(1704067200123).unwrap();
+        assert_eq!(time.to_rfc3339(), "2024-01-01T00:00:00.123+00:00");
+        assert_eq!(time.to_unix_millis(), Some(1704067200123));
+        let before = Utc.with_ymd_and_hms(1969, 12, 31, 0, 0, 0).unwrap();
+        assert_eq!(before.to_unix_millis(), None);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time_roundtrip() {
+        use time::OffsetDateTime;
+
+        let time = OffsetDateTime::from_unix_millis(1704067200123).unwrap();
+        assert_eq!(time.year(), 2024);
+        assert_eq!(time.millisecond(), 123);
+        assert_eq!(time.to_unix_millis(), Some(1704067200123));
+        assert_eq!(
+            OffsetDateTime::from_unix_timestamp(-1)
+                .unwrap()
+                .to_unix_millis(),
+            None
+        );
+    }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn test_jiff_roundtrip() {
+        use jiff::Timestamp;
+
+        let time = Timestamp::from_unix_millis(1704067200123).unwrap();
+        assert_eq!(time.to_string(), "2024-01-01T00:00:00.123Z");
+        assert_eq!(time.to_unix_millis(), Some(1704067200123));
+        assert_eq!(Timestamp::from_second(-1).unwrap().to_unix_millis(), None);
+    }
+}
@@ -0,0 +1,316 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::ops::Deref;
+
+use crate::Base62DecodeError;
+
+/// Maximum length of a base62 encoded `u64`, the size of the buffers the encoders write into
+pub const BASE62_MAX_LEN: usize = 11;
+
+/// Length of every sortable base62 ID; `u64::MAX` needs all 11 digits
+pub const BASE62_SORTABLE_LEN: usize = BASE62_MAX_LEN;
+
+/// Digits in ASCII order, so comparing encoded bytes compares the digits they stand for
+const ALPHABET: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Encode an ID into a caller-provided buffer without allocating
+///
+/// Produces the same characters as [`base62_encode`](crate::base62_encode), written to the
+/// start of `buf`; the rest of the buffer is left untouched.
+///
+/// # Returns
+/// * `&str` - The encoded ID, borrowed from `buf`
+#[inline]
+pub fn base62_encode_into(id: u64, buf: &mut [u8; BASE62_MAX_LEN]) -> &str {
+    // 11 bytes hold any u64, so encoding cannot fail
+    let len = base62::encode_bytes(id, buf).unwrap();
+    ascii_str(&buf[..len])
+}
+
+/// Encode an ID as exactly 11 base62 characters into a caller-provided buffer, see
+/// [`base62_encode_sortable`]
+#[inline]
+pub fn base62_encode_sortable_into(id: u64, buf: &mut [u8; BASE62_SORTABLE_LEN]) -> &str {
+    let mut rest = id;
+    for digit in buf.iter_mut().rev() {
+        *digit = ALPHABET[(rest % 62) as usize];
+        rest /= 62;
+    }
+    ascii_str(buf)
+}
+
+/// Write the base62 encoding of an ID to a formatter or `String` without allocating
+pub fn base62_encode_fmt<W: fmt::Write + ?Sized>(id: u64, out: &mut W) -> fmt::Result {
+    out.write_str(base62_encode_into(id, &mut [0; BASE62_MAX_LEN]))
+}
+
+/// Write the base62 encoding of an ID to a file, socket or buffer without allocating
+pub fn base62_encode_io<W: io::Write + ?Sized>(id: u64, out: &mut W) -> io::Result<()> {
+    let mut buf = [0; BASE62_MAX_LEN];
+    let len = base62_encode_into(id, &mut buf).len();
+    out.write_all(&buf[..len])
+}
+
+/// Encode an ID as exactly 11 base62 characters, left-padded with `0`
+///
+/// Unlike [`base62_encode`](crate::base62_encode), every ID has the same width, so
+/// comparing the strings (or their bytes) gives the same order as comparing the IDs. Use
+/// it for IDs stored as text keys that must sort in generation order.
+pub fn base62_encode_sortable(id: u64) -> String {
+    Base62Str::sortable(id).as_str().to_owned()
+}
+
+/// Encoder output is always ASCII
+#[inline(always)]
+fn ascii_str(bytes: &[u8]) -> &str {
+    std::str::from_utf8(bytes).unwrap()
+}
+
+/// A base62 encoded ID stored inline, without heap allocation
+///
+/// Dereferences to `&str`, and compares, hashes and prints like the string it holds.
+#[derive(Clone, Copy)]
+pub struct Base62Str {
+    buf: [u8; BASE62_MAX_LEN],
+    len: u8,
+}
+
+impl Base62Str {
+    /// Encode an ID like [`base62_encode`](crate::base62_encode)
+    #[inline]
+    pub fn new(id: u64) -> Self {
+        let mut buf = [0; BASE62_MAX_LEN];
+        let len = base62_encode_into(id, &mut buf).len() as u8;
+        Self { buf, len }
+    }
+
+    /// Encode an ID like [`base62_encode_sortable`]
+    #[inline]
+    pub fn sortable(id: u64) -> Self {
+        let mut buf = [0; BASE62_SORTABLE_LEN];
+        base62_encode_sortable_into(id, &mut buf);
+        Self {
+            buf,
+            len: BASE62_SORTABLE_LEN as u8,
+        }
+    }
+
+    /// The encoded ID
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        ascii_str(&self.buf[..self.len as usize])
+    }
+}
+
+impl Deref for Base62Str {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Base62Str {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for Base62Str {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Base62Str {}
+
+impl PartialEq<str> for Base62Str {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Base62Str {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialOrd for Base62Str {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Base62Str {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for Base62Str {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl fmt::Debug for Base62Str {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Base62Str {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl From<Base62Str> for String {
+    fn from(encoded: Base62Str) -> Self {
+        encoded.as_str().to_owned()
+    }
+}
+
+/// Decode an ID produced by [`base62_encode_sortable`]
+///
+/// # Returns
+/// * `Result<u64, Base62DecodeError>` - The ID, `InvalidLength` unless the input is exactly
+///   11 characters, `InvalidCharacter` for bytes outside `0-9A-Za-z`, or `Overflow` above
+///   `u64::MAX`
+pub fn base62_decode_sortable(encoded: &str) -> Result<u64, Base62DecodeError> {
+    if encoded.len() != BASE62_SORTABLE_LEN {
+        return Err(Base62DecodeError::InvalidLength {
+            len: encoded.len(),
+            expected: BASE62_SORTABLE_LEN,
+        });
+    }
+    encoded.bytes().try_fold(0u64, |value, c| {
+        let digit = match c {
+            b'0'..=b'9' => c - b'0',
+            b'A'..=b'Z' => c - b'A' + 10,
+            b'a'..=b'z' => c - b'a' + 36,
+            _ => return Err(Base62DecodeError::InvalidCharacter),
+        };
+        value
+            .checked_mul(62)
+            .and_then(|value| value.checked_add(digit as u64))
+            .ok_or(Base62DecodeError::Overflow)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{base62_decode, base62_encode};
+
+    #[test]
+    fn test_sortable_roundtrip() {
+        assert_eq!(base62_encode_sortable(0), "00000000000");
+        assert_eq!(base62_encode_sortable(61), "0000000000z");
+        assert_eq!(base62_encode_sortable(62), "00000000010");
+        assert_eq!(base62_encode_sortable(u64::MAX), "LygHa16AHYF");
+
+        for id in [0, 1, 61, 62, 1 << 22, 1 << 40, 1 << 63, u64::MAX] {
+            let encoded = base62_encode_sortable(id);
+            assert_eq!(encoded.len(), BASE62_SORTABLE_LEN);
+            assert_eq!(base62_decode_sortable(&encoded).unwrap(), id);
+            // Same digits as the variable-width encoding, only padded
+            assert_eq!(base62_decode(&encoded).unwrap(), id);
+            assert!(encoded.ends_with(&base62_encode(id)));
+        }
+    }
+
+    #[test]
+    fn test_string_order_matches_id_order() {
+        let mut ids: Vec<u64> = (0..64)
+            .flat_map(|shift| {
+                let bit = 1u64 << shift;
+                [bit - 1, bit, bit + 1, bit.wrapping_mul(61)]
+            })
+            .chain([u64::MAX, u64::MAX - 1])
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+
+        let mut encoded: Vec<String> = ids.iter().map(|&id| base62_encode_sortable(id)).collect();
+        encoded.sort_unstable();
+        let decoded: Vec<u64> = encoded
+            .iter()
+            .map(|s| base62_decode_sortable(s).unwrap())
+            .collect();
+        assert_eq!(decoded, ids);
+    }
+
+    #[test]
+    fn test_encode_into_buffers() {
+        for id in [0, 61, 62, 1234567890, u64::MAX] {
+            let expected = base62_encode(id);
+
+            let mut buf = [b'-'; BASE62_MAX_LEN];
+            assert_eq!(base62_encode_into(id, &mut buf), expected);
+            assert!(buf[expected.len()..].iter().all(|&c| c == b'-'));
+
+            let mut out = String::from("id=");
+            base62_encode_fmt(id, &mut out).unwrap();
+            assert_eq!(out, format!("id={expected}"));
+
+            let mut out = Vec::new();
+            base62_encode_io(id, &mut out).unwrap();
+            assert_eq!(out, expected.as_bytes());
+
+            let mut buf = [0; BASE62_SORTABLE_LEN];
+            assert_eq!(
+                base62_encode_sortable_into(id, &mut buf),
+                base62_encode_sortable(id)
+            );
+        }
+    }
+
+    #[test]
+    fn test_base62_str() {
+        let encoded = Base62Str::new(1234567890);
+        assert_eq!(encoded, base62_encode(1234567890).as_str());
+        assert_eq!(encoded.len(), 6);
+        assert_eq!(format!("{encoded}"), "1LY7VK");
+        assert_eq!(format!("{encoded:>8}"), "  1LY7VK");
+        assert_eq!(format!("{encoded:?}"), "\"1LY7VK\"");
+        assert_eq!(String::from(encoded), "1LY7VK");
+        assert_eq!(base62_decode(&encoded).unwrap(), 1234567890);
+
+        let sortable = Base62Str::sortable(1234567890);
+        assert_eq!(sortable, "000001LY7VK");
+        assert!(Base62Str::sortable(61) < Base62Str::sortable(62));
+        assert_eq!(std::mem::size_of::<Base62Str>(), 12);
+    }
+
+    #[test]
+    fn test_sortable_decode_errors() {
+        assert!(matches!(
+            base62_decode_sortable("0"),
+            Err(Base62DecodeError::InvalidLength {
+                len: 1,
+                expected: 11
+            })
+        ));
+        assert!(matches!(
+            base62_decode_sortable("000000000000"),
+            Err(Base62DecodeError::InvalidLength { len: 12, .. })
+        ));
+        assert!(matches!(
+            base62_decode_sortable("0000000000-"),
+            Err(Base62DecodeError::InvalidCharacter)
+        ));
+        assert!(matches!(
+            base62_decode_sortable("LygHa16AHYG"),
+            Err(Base62DecodeError::Overflow)
+        ));
+        assert!(matches!(
+            base62_decode_sortable("zzzzzzzzzzz"),
+            Err(Base62DecodeError::Overflow)
+        ));
+    }
+}
@@ -1,3 +1,5 @@
+use std::io;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Represents errors that can occur during SnowID operations
@@ -6,9 +8,35 @@ pub enum SnowIDError {
     /// Error when node ID exceeds the maximum allowed value
     #[error("Node ID {node_id} is invalid. Maximum allowed value is {max}")]
     InvalidNodeId { node_id: u16, max: u16 },
+    /// Error when a sequence number exceeds the maximum allowed value
+    #[error("Sequence {sequence} is invalid. Maximum allowed value is {max}")]
+    InvalidSequence { sequence: u16, max: u16 },
+    /// Error when a datacenter ID exceeds the width of its sub-field
+    #[error("Datacenter ID {datacenter} is invalid. Maximum allowed value is {max}")]
+    InvalidDatacenterId { datacenter: u16, max: u16 },
+    /// Error when a worker ID exceeds the width of its sub-field
+    #[error("Worker ID {worker} is invalid. Maximum allowed value is {max}")]
+    InvalidWorkerId { worker: u16, max: u16 },
     /// Error when clock moves backwards (system time issue)
     #[error("Clock moved backwards. Refusing to generate id for {delta} milliseconds")]
     ClockMovedBackwards { delta: i64 },
+    /// Error when every sequence slot of the current tick is taken and the caller
+    /// asked not to wait
+    #[error("Sequence exhausted for the current tick. Retry after {retry_after:?}")]
+    SequenceExhausted { retry_after: Duration },
+    /// Error when the current time no longer fits in the timestamp field of the layout
+    #[error("Timestamp {ticks} exceeds the maximum of {max} ticks. The ID layout has expired")]
+    TimestampOverflow { ticks: u64, max: u64 },
+    /// Error when the background write of the high-water mark has not caught up with the
+    /// clock and the caller asked not to wait
+    #[error("State file write is behind the clock. Retry shortly")]
+    PersistenceBehind,
+    /// Error when the high-water mark state file cannot be read or written
+    #[error("State file error ({kind}): {message}")]
+    Persistence {
+        kind: io::ErrorKind,
+        message: String,
+    },
 }
 
 #[cfg(test)]
@@ -26,11 +54,51 @@ fn test_error_display() {
             "Node ID 1024 is invalid. Maximum allowed value is 1023"
         );
 
+        let invalid_worker = SnowIDError::InvalidWorkerId {
+            worker: 32,
+            max: 31,
+        };
+        assert_eq!(
+            invalid_worker.to_string(),
+            "Worker ID 32 is invalid. Maximum allowed value is 31"
+        );
+
         let clock_backwards = SnowIDError::ClockMovedBackwards { delta: 100 };
         assert_eq!(
             clock_backwards.to_string(),
             "Clock moved backwards. Refusing to generate id for 100 milliseconds"
         );
+
+        let overflow = SnowIDError::TimestampOverflow {
+            ticks: 1024,
+            max: 1023,
+        };
+        assert_eq!(
+            overflow.to_string(),
+            "Timestamp 1024 exceeds the maximum of 1023 ticks. The ID layout has expired"
+        );
+
+        let persistence = SnowIDError::Persistence {
+            kind: io::ErrorKind::PermissionDenied,
+            message: "snowid.state: denied".to_string(),
+        };
+        assert_eq!(
+            persistence.to_string(),
+            "State file error (permission denied): snowid.state: denied"
+        );
+
+        assert_eq!(
+            SnowIDError::PersistenceBehind.to_string(),
+            "State file write is behind the clock. Retry shortly"
+        );
+
+        let exhausted = SnowIDError::SequenceExhausted {
+            retry_after: Duration::from_millis(1),
+        };
+        assert_eq!(
+            exhausted.to_string(),
+            "Sequence exhausted for the current tick. Retry after 1ms"
+        );
     }
 
     #[test]
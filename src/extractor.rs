@@ -1,4 +1,8 @@
+use std::ops::RangeInclusive;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use crate::config::SnowIDConfig;
+use crate::{Base62DecodeError, Clock, SnowIDError, UnixMillis, base62_decode};
 
 /// SnowID component extractor
 #[derive(Debug, Copy, Clone)]
@@ -8,37 +12,246 @@ pub struct SnowIDExtractor {
 
 impl SnowIDExtractor {
     /// Create a new SnowID extractor with the given configuration
-    pub(crate) fn new(config: SnowIDConfig) -> Self {
+    ///
+    /// The configuration must match the one the IDs were generated with; no generator
+    /// or node ID is needed to decode them.
+    pub const fn new(config: SnowIDConfig) -> Self {
         Self { config }
     }
 
-    /// Extract timestamp component from a SnowID
+    /// Configuration this extractor decodes IDs with
+    #[inline(always)]
+    pub const fn config(&self) -> &SnowIDConfig {
+        &self.config
+    }
+
+    /// Extract timestamp component from a SnowID in milliseconds since the custom epoch
     #[inline(always)]
     pub fn timestamp(&self, id: u64) -> u64 {
+        self.config.ticks_to_millis(self.ticks(id))
+    }
+
+    /// Extract the raw timestamp field from a SnowID, in ticks since the custom epoch
+    #[inline(always)]
+    pub fn ticks(&self, id: u64) -> u64 {
         (id >> self.config.timestamp_shift()) & self.config.timestamp_mask()
     }
 
+    /// Extract the timestamp of a SnowID in milliseconds since the Unix epoch
+    #[inline(always)]
+    pub fn unix_millis(&self, id: u64) -> u64 {
+        self.checked_unix_millis(id).unwrap_or(u64::MAX)
+    }
+
+    /// Timestamp in milliseconds since the Unix epoch, `None` if it is not representable
+    #[inline(always)]
+    pub(crate) fn checked_unix_millis(&self, id: u64) -> Option<u64> {
+        self.config
+            .checked_ticks_to_millis(self.ticks(id))?
+            .checked_add(self.config.epoch())
+    }
+
+    /// Extract the timestamp of a SnowID as a `SystemTime`
+    ///
+    /// # Returns
+    /// * `Option<SystemTime>` - The timestamp, or `None` if an untrusted ID decodes to a time
+    ///   that `u64` milliseconds or the platform's `SystemTime` cannot represent
+    #[inline]
+    pub fn system_time(&self, id: u64) -> Option<SystemTime> {
+        UNIX_EPOCH.checked_add(Duration::from_millis(self.checked_unix_millis(id)?))
+    }
+
+    /// Extract the timestamp of a SnowID as any supported date-time type, e.g.
+    /// `chrono::DateTime<Utc>` with the `chrono` feature
+    ///
+    /// # Returns
+    /// * `Option<T>` - The timestamp, or `None` if it is out of range for `T`
+    #[inline]
+    pub fn datetime<T: UnixMillis>(&self, id: u64) -> Option<T> {
+        T::from_unix_millis(self.checked_unix_millis(id)?)
+    }
+
+    /// Smallest ID that can be issued at `at`: the start of its tick with node and sequence zero
+    ///
+    /// With ticks coarser than 1ms this boundary also admits IDs issued earlier in the same tick.
+    ///
+    /// # Returns
+    /// * `Option<u64>` - The boundary ID, or `None` if `at` is before the epoch or past the
+    ///   end of the timestamp field
+    pub fn min_id_at(&self, at: impl UnixMillis) -> Option<u64> {
+        let ticks = self.ticks_at(at.to_unix_millis()?)?;
+        Some(ticks << self.config.timestamp_shift())
+    }
+
+    /// Largest ID that can be issued at `at`: its tick with every node and sequence bit set
+    ///
+    /// # Returns
+    /// * `Option<u64>` - The boundary ID, or `None` if `at` is before the epoch or past the
+    ///   end of the timestamp field
+    pub fn max_id_at(&self, at: impl UnixMillis) -> Option<u64> {
+        let ticks = self.ticks_at(at.to_unix_millis()?)?;
+        let shift = self.config.timestamp_shift();
+        Some((ticks << shift) | ((1u64 << shift) - 1))
+    }
+
+    /// Smallest ID that node `node` can issue at `at`
+    ///
+    /// # Returns
+    /// * `Option<u64>` - The boundary ID, or `None` if `at` cannot be represented or `node`
+    ///   exceeds the maximum node ID
+    pub fn min_id_at_node(&self, at: impl UnixMillis, node: u16) -> Option<u64> {
+        let base = self.min_id_at(at)?;
+        (node <= self.config.max_node_id())
+            .then(|| base | ((node as u64) << self.config.node_shift()))
+    }
+
+    /// Largest ID that node `node` can issue at `at`
+    ///
+    /// # Returns
+    /// * `Option<u64>` - The boundary ID, or `None` if `at` cannot be represented or `node`
+    ///   exceeds the maximum node ID
+    pub fn max_id_at_node(&self, at: impl UnixMillis, node: u16) -> Option<u64> {
+        let base = self.min_id_at_node(at, node)?;
+        Some(base | self.config.max_sequence_id() as u64)
+    }
+
+    /// Every ID that can be issued from `start` through `end`, inclusive, for range scans such
+    /// as `WHERE id BETWEEN a AND b`
+    ///
+    /// # Returns
+    /// * `Option<RangeInclusive<u64>>` - The ID range, or `None` if either bound cannot be
+    ///   represented or `start` is after `end`
+    pub fn id_range(
+        &self,
+        start: impl UnixMillis,
+        end: impl UnixMillis,
+    ) -> Option<RangeInclusive<u64>> {
+        let min = self.min_id_at(start)?;
+        let max = self.max_id_at(end)?;
+        (min <= max).then_some(min..=max)
+    }
+
+    /// Tick containing Unix millisecond `unix_millis`, if the layout can represent it
+    #[inline]
+    fn ticks_at(&self, unix_millis: u64) -> Option<u64> {
+        let ticks = self
+            .config
+            .millis_to_ticks(unix_millis.checked_sub(self.config.epoch())?);
+        (ticks <= self.config.timestamp_mask()).then_some(ticks)
+    }
+
+    /// Time elapsed between the timestamps of two SnowIDs, regardless of their order
+    #[inline]
+    pub fn duration_between(&self, a: u64, b: u64) -> Duration {
+        Duration::from_millis(self.timestamp(a).abs_diff(self.timestamp(b)))
+    }
+
+    /// Time elapsed since a SnowID was issued, according to `clock`.
+    /// IDs stamped after the clock's current time have an age of zero.
+    #[inline]
+    pub fn age(&self, id: u64, clock: impl Clock) -> Duration {
+        Duration::from_millis(clock.now_millis().saturating_sub(self.unix_millis(id)))
+    }
+
     /// Extract node component from a SnowID
     #[inline(always)]
     pub fn node(&self, id: u64) -> u16 {
         ((id >> self.config.node_shift()) & self.config.node_mask() as u64) as u16
     }
 
+    /// Extract the datacenter sub-field of the node component
+    #[inline(always)]
+    pub fn datacenter(&self, id: u64) -> u16 {
+        self.config.node_id_parts(self.node(id)).0
+    }
+
+    /// Extract the worker sub-field of the node component
+    #[inline(always)]
+    pub fn worker(&self, id: u64) -> u16 {
+        self.config.node_id_parts(self.node(id)).1
+    }
+
     /// Extract sequence component from a SnowID
     #[inline(always)]
     pub fn sequence(&self, id: u64) -> u16 {
         (id & self.config.sequence_mask() as u64) as u16
     }
 
-    /// Decompose SnowID into its components: timestamp, node ID, and sequence
+    /// Decompose SnowID into its components: timestamp (ms since epoch), node ID, and sequence
     /// Optimized to extract all components in a single pass
     #[inline]
     pub fn decompose(&self, id: u64) -> (u64, u16, u16) {
-        let timestamp = (id >> self.config.timestamp_shift()) & self.config.timestamp_mask();
+        let ticks = (id >> self.config.timestamp_shift()) & self.config.timestamp_mask();
+        let timestamp = self.config.ticks_to_millis(ticks);
         let node = ((id >> self.config.node_shift()) & self.config.node_mask() as u64) as u16;
         let sequence = (id & self.config.sequence_mask() as u64) as u16;
         (timestamp, node, sequence)
     }
+
+    /// Build an ID from its components, the inverse of `decompose`
+    ///
+    /// # Arguments
+    /// * `timestamp` - Milliseconds since the custom epoch, rounded down to the start of its tick
+    /// * `node` - Node ID
+    /// * `sequence` - Sequence number within the tick
+    ///
+    /// # Returns
+    /// * `Result<u64, SnowIDError>` - The ID, or `TimestampOverflow`, `InvalidNodeId` or
+    ///   `InvalidSequence` for a component that does not fit its field
+    pub fn compose(&self, timestamp: u64, node: u16, sequence: u16) -> Result<u64, SnowIDError> {
+        let ticks = self.config.millis_to_ticks(timestamp);
+        if ticks > self.config.timestamp_mask() {
+            return Err(SnowIDError::TimestampOverflow {
+                ticks,
+                max: self.config.timestamp_mask(),
+            });
+        }
+        if node > self.config.max_node_id() {
+            return Err(SnowIDError::InvalidNodeId {
+                node_id: node,
+                max: self.config.max_node_id(),
+            });
+        }
+        if sequence > self.config.max_sequence_id() {
+            return Err(SnowIDError::InvalidSequence {
+                sequence,
+                max: self.config.max_sequence_id(),
+            });
+        }
+        Ok((ticks << self.config.timestamp_shift())
+            | ((node as u64) << self.config.node_shift())
+            | sequence as u64)
+    }
+
+    /// Decode a base62 encoded SnowID back to its raw u64 value
+    ///
+    /// # Arguments
+    /// * `encoded` - The base62 encoded SnowID string
+    ///
+    /// # Returns
+    /// * `Result<u64, Base62DecodeError>` - The decoded u64 SnowID or an error
+    pub fn decode_base62(&self, encoded: &str) -> Result<u64, Base62DecodeError> {
+        base62_decode(encoded)
+    }
+
+    /// Decompose a base62 encoded SnowID into its components: timestamp (ms since epoch),
+    /// node ID, and sequence
+    ///
+    /// # Arguments
+    /// * `encoded` - The base62 encoded SnowID string
+    ///
+    /// # Returns
+    /// * `Result<(u64, u16, u16), Base62DecodeError>` - Tuple containing the components or an error
+    pub fn decompose_base62(&self, encoded: &str) -> Result<(u64, u16, u16), Base62DecodeError> {
+        let id = self.decode_base62(encoded)?;
+        Ok(self.decompose(id))
+    }
+}
+
+impl From<SnowIDConfig> for SnowIDExtractor {
+    fn from(config: SnowIDConfig) -> Self {
+        Self::new(config)
+    }
 }
 
 #[cfg(test)]
@@ -46,10 +259,10 @@ mod tests {
     use super::*;
     use crate::SnowID;
 
-    fn create_snow_id(config: SnowIDConfig, timestamp: u64, node: u16, sequence: u16) -> u64 {
-        ((timestamp & config.timestamp_mask()) << config.timestamp_shift())
-            | ((node as u64 & config.node_mask() as u64) << config.node_shift())
-            | (sequence as u64 & config.sequence_mask() as u64)
+    fn create_snow_id(config: SnowIDConfig, ticks: u64, node: u16, sequence: u16) -> u64 {
+        SnowIDExtractor::new(config)
+            .compose(config.ticks_to_millis(ticks), node, sequence)
+            .unwrap()
     }
 
     #[test]
@@ -62,7 +275,7 @@ fn test_decompose() {
         let node: u16 = 42;
         let sequence: u16 = 123;
 
-        // Create SnowID using the generator's internal method
+        // Build the ID from known components with the public `compose`
         let id = create_snow_id(config, timestamp, node, sequence);
 
         // Test individual component extraction
@@ -77,6 +290,156 @@ fn test_decompose() {
         assert_eq!(ext_sequence, sequence);
     }
 
+    #[test]
+    fn test_compose_roundtrip() {
+        let config = SnowIDConfig::builder()
+            .timestamp_bits(41)
+            .unwrap()
+            .node_bits(13)
+            .unwrap()
+            .sequence_bits(10)
+            .unwrap()
+            .build()
+            .unwrap();
+        let extract = SnowIDExtractor::new(config);
+
+        let id = extract.compose(86_400_000, 8191, 1023).unwrap();
+        assert_eq!(id, (86_400_000 << 23) | (8191 << 10) | 1023);
+        assert_eq!(extract.decompose(id), (86_400_000, 8191, 1023));
+    }
+
+    #[test]
+    fn test_compose_rejects_oversized_components() {
+        let config = SnowIDConfig::default();
+        let extract = SnowIDExtractor::new(config);
+
+        assert_eq!(
+            extract.compose(1 << 42, 0, 0),
+            Err(SnowIDError::TimestampOverflow {
+                ticks: 1 << 42,
+                max: (1 << 42) - 1
+            })
+        );
+        assert_eq!(
+            extract.compose(0, 1024, 0),
+            Err(SnowIDError::InvalidNodeId {
+                node_id: 1024,
+                max: 1023
+            })
+        );
+        assert_eq!(
+            extract.compose(0, 0, 4096),
+            Err(SnowIDError::InvalidSequence {
+                sequence: 4096,
+                max: 4095
+            })
+        );
+
+        // Coarse ticks round down to the start of the tick
+        let coarse = SnowIDConfig::builder()
+            .tick(std::time::Duration::from_millis(10))
+            .unwrap()
+            .build()
+            .unwrap();
+        let id = SnowIDExtractor::new(coarse).compose(1_234, 1, 0).unwrap();
+        assert_eq!(SnowIDExtractor::new(coarse).timestamp(id), 1_230);
+    }
+
+    #[test]
+    fn test_timestamp_converts_ticks_to_millis() {
+        let config = SnowIDConfig::builder()
+            .tick(std::time::Duration::from_secs(1))
+            .unwrap()
+            .build()
+            .unwrap();
+        let snowid_gen = SnowID::with_config(7, config).unwrap();
+
+        let id = create_snow_id(config, 1_234, 7, 5);
+        assert_eq!(snowid_gen.extract.ticks(id), 1_234);
+        assert_eq!(snowid_gen.extract.timestamp(id), 1_234_000);
+        assert_eq!(snowid_gen.extract.decompose(id), (1_234_000, 7, 5));
+    }
+
+    #[test]
+    fn test_absolute_time() {
+        let config = SnowIDConfig::default();
+        let extract = SnowIDExtractor::new(config);
+        let a = create_snow_id(config, 1_000, 1, 0);
+        let b = create_snow_id(config, 3_500, 2, 9);
+
+        assert_eq!(extract.unix_millis(a), config.epoch() + 1_000);
+        assert_eq!(
+            extract.system_time(b),
+            Some(UNIX_EPOCH + Duration::from_millis(config.epoch() + 3_500))
+        );
+        assert_eq!(extract.duration_between(a, b), Duration::from_millis(2_500));
+        assert_eq!(extract.duration_between(b, a), Duration::from_millis(2_500));
+    }
+
+    #[test]
+    fn test_boundary_ids() {
+        let config = SnowIDConfig::default();
+        let extract = SnowIDExtractor::new(config);
+        let at = UNIX_EPOCH + Duration::from_millis(config.epoch() + 5_000);
+
+        let min = extract.min_id_at(at).unwrap();
+        let max = extract.max_id_at(at).unwrap();
+        assert_eq!(min, create_snow_id(config, 5_000, 0, 0));
+        assert_eq!(
+            max,
+            create_snow_id(
+                config,
+                5_000,
+                config.max_node_id(),
+                config.max_sequence_id()
+            )
+        );
+        assert_eq!(max + 1, create_snow_id(config, 5_001, 0, 0));
+        assert_eq!(extract.datetime::<SystemTime>(min), Some(at));
+
+        let before_epoch = UNIX_EPOCH + Duration::from_millis(config.epoch() - 1);
+        assert_eq!(extract.min_id_at(before_epoch), None);
+        let past_end = UNIX_EPOCH + Duration::from_millis(config.epoch() + (1 << 42));
+        assert_eq!(extract.max_id_at(past_end), None);
+    }
+
+    #[test]
+    fn test_age() {
+        let config = SnowIDConfig::default();
+        let extract = SnowIDExtractor::new(config);
+        let clock = crate::tests::ManualClock::new(config.epoch() + 10_000);
+        let id = create_snow_id(config, 4_000, 1, 0);
+
+        assert_eq!(extract.age(id, &clock), Duration::from_secs(6));
+        clock.set(config.epoch() + 1_000);
+        assert_eq!(extract.age(id, &clock), Duration::ZERO);
+
+        let live = SnowID::new(1).unwrap();
+        assert!(live.extract.age(live.generate(), crate::SystemClock) < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_standalone_extractor() {
+        let config = SnowIDConfig::builder()
+            .node_bits(12)
+            .unwrap()
+            .build()
+            .unwrap();
+        let generator = SnowID::with_config(4000, config).unwrap();
+        let (encoded, id) = generator.generate_base62_with_raw();
+
+        // Decoding only needs the configuration, not a generator
+        let extract = SnowIDExtractor::new(config);
+        assert_eq!(extract.config().node_bits(), 12);
+        assert_eq!(extract.decode_base62(&encoded).unwrap(), id);
+        assert_eq!(
+            extract.decompose_base62(&encoded).unwrap(),
+            extract.decompose(id)
+        );
+        assert_eq!(SnowIDExtractor::from(config).node(id), 4000);
+        assert!(extract.decompose_base62("not base62!").is_err());
+    }
+
     #[test]
     fn test_component_boundaries() {
         let config = SnowIDConfig::default();
@@ -0,0 +1,197 @@
+use std::fmt;
+use std::num::{ParseIntError, TryFromIntError};
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use crate::{Base62DecodeError, Base62Str, SnowIDConfig, SnowIDExtractor, base62_decode};
+
+/// A SnowID value, as returned by [`SnowID::generate_id`](crate::SnowID::generate_id)
+///
+/// A transparent `u64`: it sorts in generation order, hashes and compares like the number,
+/// and costs nothing over it. The ID does not know its layout, so the component accessors
+/// take the configuration it was generated with.
+///
+/// `Display` and `FromStr` use the decimal form; base62 goes through
+/// [`SnowId::to_base62`] and [`SnowId::from_base62`]. Strings are never guessed to be one
+/// or the other, since a base62 ID made only of digits is also a valid decimal number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct SnowId(u64);
+
+impl SnowId {
+    /// Wrap a raw ID
+    #[inline(always)]
+    pub const fn from_raw(value: u64) -> Self {
+        Self(value)
+    }
+
+    /// Parse the decimal form, as printed by `Display`
+    pub fn from_decimal(input: &str) -> Result<Self, ParseIntError> {
+        input.parse().map(Self)
+    }
+
+    /// Decode the base62 form, as produced by `to_base62` or `generate_base62`
+    pub fn from_base62(encoded: &str) -> Result<Self, Base62DecodeError> {
+        base62_decode(encoded).map(Self)
+    }
+
+    /// Raw numeric value
+    #[inline(always)]
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// Base62 representation, as produced by `generate_base62`
+    #[inline]
+    pub fn to_base62(&self) -> Base62Str {
+        Base62Str::new(self.0)
+    }
+
+    /// Timestamp in milliseconds since the custom epoch of `config`
+    #[inline]
+    pub fn timestamp(&self, config: &SnowIDConfig) -> u64 {
+        SnowIDExtractor::new(*config).timestamp(self.0)
+    }
+
+    /// Timestamp in milliseconds since the Unix epoch
+    #[inline]
+    pub fn unix_millis(&self, config: &SnowIDConfig) -> u64 {
+        SnowIDExtractor::new(*config).unix_millis(self.0)
+    }
+
+    /// Timestamp as a `SystemTime`, `None` if it is not representable
+    #[inline]
+    pub fn system_time(&self, config: &SnowIDConfig) -> Option<SystemTime> {
+        SnowIDExtractor::new(*config).system_time(self.0)
+    }
+
+    /// Node ID component
+    #[inline]
+    pub fn node(&self, config: &SnowIDConfig) -> u16 {
+        SnowIDExtractor::new(*config).node(self.0)
+    }
+
+    /// Sequence component
+    #[inline]
+    pub fn sequence(&self, config: &SnowIDConfig) -> u16 {
+        SnowIDExtractor::new(*config).sequence(self.0)
+    }
+}
+
+impl fmt::Display for SnowId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// Parses the decimal form; see [`SnowId::from_base62`] for base62
+impl FromStr for SnowId {
+    type Err = ParseIntError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::from_decimal(input)
+    }
+}
+
+impl From<u64> for SnowId {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<SnowId> for u64 {
+    fn from(id: SnowId) -> Self {
+        id.0
+    }
+}
+
+/// Fails for negative values
+impl TryFrom<i64> for SnowId {
+    type Error = TryFromIntError;
+
+    fn try_from(value: i64) -> Result<Self, Self::Error> {
+        u64::try_from(value).map(Self)
+    }
+}
+
+/// Fails if the most significant bit is set; reserve the sign bit to rule this out
+impl TryFrom<SnowId> for i64 {
+    type Error = TryFromIntError;
+
+    fn try_from(id: SnowId) -> Result<Self, Self::Error> {
+        i64::try_from(id.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SnowID;
+    use std::mem::size_of;
+
+    #[test]
+    fn test_generated_id_accessors() {
+        let config = SnowIDConfig::builder()
+            .node_bits(12)
+            .unwrap()
+            .build()
+            .unwrap();
+        let generator = SnowID::with_config(4000, config).unwrap();
+        let id = generator.generate_id();
+
+        assert_eq!(size_of::<SnowId>(), size_of::<u64>());
+        assert_eq!(id.node(&config), 4000);
+        assert_eq!(
+            (
+                id.timestamp(&config),
+                id.node(&config),
+                id.sequence(&config)
+            ),
+            generator.extract.decompose(id.as_u64())
+        );
+        assert_eq!(
+            id.unix_millis(&config),
+            config.epoch() + id.timestamp(&config)
+        );
+        assert_eq!(
+            id.system_time(&config),
+            generator.extract.system_time(id.as_u64())
+        );
+    }
+
+    #[test]
+    fn test_display_and_parse() {
+        let generator = SnowID::new(1).unwrap();
+        let id = generator.generate_id();
+
+        let decimal = id.to_string();
+        assert_eq!(decimal, id.as_u64().to_string());
+        assert_eq!(decimal.parse::<SnowId>(), Ok(id));
+        assert_eq!(SnowId::from_decimal(&decimal), Ok(id));
+        assert_eq!(SnowId::from_base62(&id.to_base62()).unwrap(), id);
+
+        // Digit-only strings are valid in both forms and decode to different values
+        assert_eq!(SnowId::from_decimal("123"), Ok(SnowId::from_raw(123)));
+        assert_eq!(SnowId::from_base62("123").unwrap(), SnowId::from_raw(3_971));
+        assert!("99999999999999999999".parse::<SnowId>().is_err());
+        assert!(SnowId::from_base62("not-an-id").is_err());
+    }
+
+    #[test]
+    fn test_ordering_follows_generation() {
+        let generator = SnowID::new(1).unwrap();
+        let ids: Vec<SnowId> = (0..100).map(|_| generator.generate_id()).collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_integer_conversions() {
+        let id = SnowId::from(42u64);
+        assert_eq!(u64::from(id), 42);
+        assert_eq!(i64::try_from(id), Ok(42));
+        assert_eq!(SnowId::try_from(42i64).unwrap(), id);
+
+        assert!(SnowId::try_from(-1i64).is_err());
+        assert!(i64::try_from(SnowId::from(u64::MAX)).is_err());
+    }
+}
@@ -1,26 +1,48 @@
 #![forbid(unsafe_code)]
 
-use std::sync::atomic::{AtomicU16, AtomicU64, Ordering};
-use std::thread;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::Duration;
 
+use crate::sync::{AtomicU64, Ordering};
+
+#[cfg(feature = "async")]
+mod async_generate;
+mod capacity;
+mod clock;
 mod config;
+mod datetime;
+mod encoding;
 mod error;
 mod extractor;
+mod id;
+mod persist;
+mod prefixed;
+mod sync;
 #[cfg(test)]
 pub mod tests;
-
-pub use config::SnowIDConfig;
+mod typed;
+mod validate;
+
+pub use capacity::CapacityRequirements;
+pub use clock::{Clock, SystemClock};
+pub use config::{ClockRegressionPolicy, SnowIDConfig, SnowIDConfigBuilder, SnowIDConfigError};
+pub use datetime::UnixMillis;
+pub use encoding::{
+    BASE62_MAX_LEN, BASE62_SORTABLE_LEN, Base62Str, base62_decode_sortable, base62_encode_fmt,
+    base62_encode_into, base62_encode_io, base62_encode_sortable, base62_encode_sortable_into,
+};
 pub use error::SnowIDError;
 pub use extractor::SnowIDExtractor;
-
-/// Re-export base62 encode function from the external crate with appropriate type conversions
+pub use id::SnowId;
+pub use persist::StateFile;
+pub use prefixed::{Prefixed, PrefixedIdError};
+pub use typed::{Id, TypedSnowID};
+pub use validate::{IdProblem, InvalidId, ValidationRules};
+
+/// Re-export base62 encode function from the external crate with appropriate type conversions.
+/// Allocates a `String`; see `base62_encode_into`, `Base62Str` and `base62_encode_fmt` for
+/// allocation-free alternatives.
 pub fn base62_encode(id: u64) -> String {
-    // The maximum size needed for u64 in base62 is 11 bytes
-    let mut buf = [0u8; 11];
-    let len = base62::encode_bytes(id, &mut buf).unwrap();
-    // base62 output is always valid ASCII
-    std::str::from_utf8(&buf[..len]).unwrap().to_owned()
+    Base62Str::new(id).into()
 }
 
 /// Decode a base62 string to a u64, handling potential overflow
@@ -55,14 +77,30 @@ pub enum Base62DecodeError {
     #[error("Decoded value would overflow u64")]
     Overflow,
 
+    #[error("Invalid input: expected {expected} characters, got {len}")]
+    InvalidLength { len: usize, expected: usize },
+
     #[error("Base62 decode error: {0}")]
     Other(#[from] base62::DecodeError),
 }
 
+/// Outcome of a single non-blocking attempt to claim sequence slots
+#[derive(Debug, Clone, Copy)]
+enum Reservation {
+    /// `count` consecutive slots starting at `first_seq` were claimed in millisecond `ts`
+    Claimed { ts: u64, first_seq: u16, count: u32 },
+    /// Every slot of tick `last_ts` is taken; the clock must advance by `wait_ms` first
+    Exhausted { last_ts: u64, wait_ms: u64 },
+    /// The clock is `delta` ms behind the last tick and the policy asks to wait
+    Behind { delta: u64 },
+    /// The state file has not yet recorded a mark covering the tick to be issued
+    Unpersisted,
+}
+
 /// Main ID generator with cache-line alignment to prevent false sharing
 #[derive(Debug)]
 #[repr(align(64))]
-pub struct SnowID {
+pub struct SnowID<C = SystemClock> {
     /// Node ID for this generator
     pub node_id: u16,
 
@@ -72,15 +110,22 @@ pub struct SnowID {
     /// Extractor for decomposing IDs
     pub extract: SnowIDExtractor,
 
-    /// Last timestamp used to generate an ID (hot atomic, cache-line aligned)
-    last_timestamp: AtomicU64,
+    /// Time source used for every timestamp read
+    clock: C,
+
+    /// Optional high-water mark file guarding against reissue after a restart
+    persistence: Option<persist::Persistence>,
 
-    /// Sequence counter for IDs generated in the same millisecond (hot atomic)
-    sequence: AtomicU16,
+    /// Last issued timestamp and sequence packed into one word (hot atomic, cache-line aligned).
+    /// Layout is `(timestamp << sequence_bits) | sequence`, so every transition is a single CAS
+    /// and the packed value strictly increases with each issued ID.
+    state: AtomicU64,
 }
 
 impl SnowID {
+    /// Timestamp width of the default layout; see [`SnowIDConfig::timestamp_bits`]
     pub const TIMESTAMP_BITS: u32 = 42;
+    /// Node plus sequence width of the default layout
     pub const TOTAL_NODE_AND_SEQUENCE_BITS: u8 = 22;
     const MAX_BACKOFF_MS: u64 = 100;
 
@@ -106,6 +151,54 @@ pub fn new(node_id: u16) -> Result<Self, SnowIDError> {
     /// # Returns
     /// * `Result<SnowID, Error>` - New SnowID generator or error if node_id is invalid
     pub fn with_config(node_id: u16, config: SnowIDConfig) -> Result<Self, SnowIDError> {
+        Self::with_clock(node_id, config, SystemClock)
+    }
+
+    /// Create a new SnowID generator from a datacenter and worker ID with default configuration
+    /// (5 datacenter bits and 5 worker bits)
+    ///
+    /// # Arguments
+    ///
+    /// * `datacenter` - Datacenter ID stored in the high node bits
+    /// * `worker` - Worker ID stored in the low node bits
+    ///
+    /// # Returns
+    /// * `Result<SnowID, Error>` - New SnowID generator or error if either part is out of range
+    pub fn with_parts(datacenter: u16, worker: u16) -> Result<Self, SnowIDError> {
+        Self::with_parts_and_config(datacenter, worker, SnowIDConfig::default())
+    }
+
+    /// Create a new SnowID generator from a datacenter and worker ID with custom configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `datacenter` - Datacenter ID stored in the high node bits
+    /// * `worker` - Worker ID stored in the low node bits
+    /// * `config` - Custom configuration defining the datacenter/worker split
+    ///
+    /// # Returns
+    /// * `Result<SnowID, Error>` - New SnowID generator or error if either part is out of range
+    pub fn with_parts_and_config(
+        datacenter: u16,
+        worker: u16,
+        config: SnowIDConfig,
+    ) -> Result<Self, SnowIDError> {
+        Self::with_config(config.node_id_from_parts(datacenter, worker)?, config)
+    }
+}
+
+impl<C: Clock> SnowID<C> {
+    /// Create a new SnowID generator with custom configuration and time source
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - Node ID to use in generated IDs
+    /// * `config` - Custom configuration
+    /// * `clock` - Clock used to read the current time
+    ///
+    /// # Returns
+    /// * `Result<SnowID<C>, Error>` - New SnowID generator or error if node_id is invalid
+    pub fn with_clock(node_id: u16, config: SnowIDConfig, clock: C) -> Result<Self, SnowIDError> {
         // Validate node ID
         let max_node_id = config.max_node_id();
         if node_id > max_node_id {
@@ -119,148 +212,408 @@ pub fn with_config(node_id: u16, config: SnowIDConfig) -> Result<Self, SnowIDErr
             node_id,
             config,
             extract: SnowIDExtractor::new(config),
-            last_timestamp: AtomicU64::new(0),
-            sequence: AtomicU16::new(0),
+            clock,
+            persistence: None,
+            state: AtomicU64::new(0),
         })
     }
 
+    /// Persist the high-water mark of issued timestamps to `state_file`
+    ///
+    /// If the file holds a mark from a previous run, no ID is issued until the clock passes
+    /// the mark plus the safety margin. Until then the generator behaves as if the clock had
+    /// moved backwards: the `ClockRegressionPolicy` decides whether to wait or fail.
+    ///
+    /// The mark is written by a background thread, so generation only waits for the disk if
+    /// that write falls a whole safety margin behind the clock.
+    ///
+    /// # Returns
+    /// * `Result<SnowID<C>, Error>` - The generator or a `Persistence` error if the file
+    ///   cannot be read or written, or the safety margin is shorter than the flush interval,
+    ///   and `TimestampOverflow` if the persisted mark lies past the end of the layout
+    pub fn with_state_file(mut self, state_file: StateFile) -> Result<Self, SnowIDError> {
+        let (persistence, floor) = persist::Persistence::open(state_file, self.clock.now_millis())?;
+        if let Some(floor) = floor
+            && floor > self.config.epoch() + 1
+        {
+            // Mark every slot up to the floor as issued, so the next ID starts at the floor
+            let last_ts = self.config.millis_to_ticks(floor - self.config.epoch() - 1);
+            // A mark from another layout or a corrupted file must not spill into the sequence bits
+            if last_ts > self.config.timestamp_mask() {
+                return Err(SnowIDError::TimestampOverflow {
+                    ticks: last_ts,
+                    max: self.config.timestamp_mask(),
+                });
+            }
+            let restored = self.pack_state(last_ts, self.config.max_sequence_id());
+            self.state.fetch_max(restored, Ordering::AcqRel);
+        }
+        self.persistence = Some(persistence);
+        Ok(self)
+    }
+
+    /// Clock used by this generator
+    #[inline(always)]
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Time left, by this generator's clock, until the timestamp field overflows and
+    /// generation starts failing with `TimestampOverflow`
+    pub fn remaining_lifetime(&self) -> Duration {
+        Duration::from_millis(
+            self.config
+                .expires_at_millis()
+                .saturating_sub(self.clock.now_millis()),
+        )
+    }
+
     /// Generate a new SnowID
     ///
     /// # Returns
     /// * `u64` - New SnowID value
+    ///
+    /// # Panics
+    /// Panics if the configured `ClockRegressionPolicy` rejects a clock regression or the
+    /// timestamp field has overflowed. Use `try_generate` to handle errors.
     #[inline]
     pub fn generate(&self) -> u64 {
-        // Fast path: check timestamp initialization first to avoid sequence waste
-        let last_ts = self.last_timestamp.load(Ordering::Acquire);
+        match self.try_generate() {
+            Ok(id) => id,
+            Err(err) => panic!("SnowID generation failed: {err}"),
+        }
+    }
 
-        // If timestamp is not initialized, go to slow path (without consuming sequence)
-        if last_ts == 0 {
-            return self.generate_slow_path();
+    /// Generate a new SnowID, reporting clock regressions instead of panicking
+    ///
+    /// # Returns
+    /// * `Result<u64, SnowIDError>` - New SnowID value, `ClockMovedBackwards` if the configured
+    ///   `ClockRegressionPolicy` refuses to issue an ID, or `TimestampOverflow` once the
+    ///   timestamp field is exhausted
+    #[inline]
+    pub fn try_generate(&self) -> Result<u64, SnowIDError> {
+        if let Some(id) = self.try_fast_path() {
+            return Ok(id);
         }
 
-        // Fast path: try to get sequence in current millisecond with AcqRel ordering
-        let seq = self.sequence.fetch_add(1, Ordering::AcqRel);
+        // Slow path: contention, millisecond change, sequence exhaustion or clock regression
+        self.generate_slow_path()
+    }
+
+    /// Generate a new SnowID as a typed `SnowId`
+    ///
+    /// # Panics
+    /// Panics under the same conditions as `generate`.
+    #[inline]
+    pub fn generate_id(&self) -> SnowId {
+        SnowId::from_raw(self.generate())
+    }
+
+    /// Generate a new typed `SnowId`, reporting errors instead of panicking
+    #[inline]
+    pub fn try_generate_id(&self) -> Result<SnowId, SnowIDError> {
+        Ok(SnowId::from_raw(self.try_generate()?))
+    }
 
-        // Fast path: if sequence is available, return immediately
-        if seq < self.config.max_sequence_id() {
-            return self.create_snowid(last_ts, seq + 1);
+    /// Generate a new SnowID without ever blocking the calling thread
+    ///
+    /// Instead of waiting for the next millisecond when the sequence is exhausted, this
+    /// returns `SequenceExhausted` with the time after which a retry can succeed. A clock
+    /// regression that `ClockRegressionPolicy::Wait` would tolerate is reported as
+    /// `ClockMovedBackwards`, and a state file write that has fallen behind as
+    /// `PersistenceBehind`, since waiting is left to the caller.
+    ///
+    /// # Returns
+    /// * `Result<u64, SnowIDError>` - New SnowID value or the reason none could be issued now
+    #[inline]
+    pub fn try_generate_now(&self) -> Result<u64, SnowIDError> {
+        if let Some(id) = self.try_fast_path() {
+            return Ok(id);
         }
 
-        // Slow path: sequence exhausted or need timestamp update
-        self.generate_slow_path()
+        match self.try_reserve(1)? {
+            Reservation::Claimed { ts, first_seq, .. } => Ok(self.create_snowid(ts, first_seq)),
+            Reservation::Exhausted { wait_ms, .. } => Err(SnowIDError::SequenceExhausted {
+                retry_after: Duration::from_millis(wait_ms),
+            }),
+            Reservation::Behind { delta } => Err(SnowIDError::ClockMovedBackwards {
+                delta: delta as i64,
+            }),
+            Reservation::Unpersisted => Err(SnowIDError::PersistenceBehind),
+        }
+    }
+
+    /// Fast path: next sequence slot in the current tick, claimed with one CAS
+    #[inline(always)]
+    fn try_fast_path(&self) -> Option<u64> {
+        let state = self.state.load(Ordering::Acquire);
+        let (last_ts, seq) = self.unpack_state(state);
+
+        // An uninitialized state (0) always goes through the slow path to read the clock
+        let claimed = state != 0
+            && seq < self.config.max_sequence_id()
+            && self.fast_path_clock_matches(last_ts)
+            && self
+                .state
+                .compare_exchange_weak(state, state + 1, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok();
+
+        claimed.then(|| self.create_snowid(last_ts, seq + 1))
+    }
+
+    /// Whether the fast path may reuse tick `last_ts` without going through the slow path
+    ///
+    /// With fresh timestamps the clock must still read `last_ts`. Without them the last tick is
+    /// reused as long as the clock has not moved behind it, so `Fail` and `Wait` still see every
+    /// regression; only `Clamp`, which would reuse the tick anyway, skips the clock read.
+    #[inline(always)]
+    fn fast_path_clock_matches(&self, last_ts: u64) -> bool {
+        if self.config.fresh_timestamps() {
+            self.get_time_since_epoch() == last_ts
+        } else {
+            matches!(
+                self.config.clock_regression_policy(),
+                ClockRegressionPolicy::Clamp
+            ) || self.get_time_since_epoch() >= last_ts
+        }
     }
 
     /// Slow path for ID generation when fast path fails
     #[cold]
     #[inline(never)]
-    fn generate_slow_path(&self) -> u64 {
-        let mut backoff_ms = 1u64;
+    fn generate_slow_path(&self) -> Result<u64, SnowIDError> {
+        let (ts, seq, _) = self.reserve(1)?;
+        Ok(self.create_snowid(ts, seq))
+    }
 
-        loop {
-            // Read the current time and last seen timestamp
-            let now = self.get_time_since_epoch();
-            let last_ts = self.last_timestamp.load(Ordering::Acquire);
+    /// Generate `count` SnowIDs in strictly increasing order
+    ///
+    /// Sequence slots are reserved in contiguous blocks with one atomic step per
+    /// millisecond, spilling into following milliseconds as needed.
+    ///
+    /// # Panics
+    /// Panics under the same conditions as `generate`.
+    pub fn generate_batch(&self, count: usize) -> Vec<u64> {
+        let mut ids = vec![0; count];
+        self.generate_into(&mut ids);
+        ids
+    }
 
-            // Clamp to last seen to ensure monotonic timestamp under clock regression
-            let ts = now.max(last_ts);
+    /// Fill `out` with SnowIDs in strictly increasing order
+    ///
+    /// # Panics
+    /// Panics under the same conditions as `generate`.
+    pub fn generate_into(&self, out: &mut [u64]) {
+        if let Err(err) = self.try_generate_into(out) {
+            panic!("SnowID generation failed: {err}");
+        }
+    }
 
-            if ts > last_ts {
-                // Try to move the generator to the new millisecond
-                if let Some(id) = self.try_advance_timestamp(last_ts, ts) {
-                    return id;
+    /// Fill `out` with SnowIDs in strictly increasing order, reporting clock regressions
+    ///
+    /// On error, the IDs written before the failure are valid and unique, the rest of
+    /// `out` is left untouched.
+    pub fn try_generate_into(&self, out: &mut [u64]) -> Result<(), SnowIDError> {
+        let mut filled = 0;
+        while filled < out.len() {
+            let wanted = u32::try_from(out.len() - filled).unwrap_or(u32::MAX);
+            let (ts, first_seq, count) = self.reserve(wanted)?;
+            filled += self.fill_reserved(&mut out[filled..], ts, first_seq, count);
+        }
+        Ok(())
+    }
+
+    /// Write the IDs of a reserved block to the front of `out`, returning how many were written
+    #[inline]
+    fn fill_reserved(&self, out: &mut [u64], ts: u64, first_seq: u16, count: u32) -> usize {
+        let count = count as usize;
+        for (offset, slot) in out[..count].iter_mut().enumerate() {
+            *slot = self.create_snowid(ts, first_seq + offset as u16);
+        }
+        count
+    }
+
+    /// Reserve up to `wanted` consecutive sequence slots within a single tick
+    ///
+    /// Blocks while the current tick is exhausted or the clock regression policy
+    /// asks to wait. Returns the timestamp, first sequence number and number of slots claimed.
+    fn reserve(&self, wanted: u32) -> Result<(u64, u16, u32), SnowIDError> {
+        let mut backoff_ms = 1u64;
+
+        loop {
+            match self.try_reserve(wanted)? {
+                Reservation::Claimed {
+                    ts,
+                    first_seq,
+                    count,
+                } => return Ok((ts, first_seq, count)),
+                Reservation::Behind { delta } => {
+                    // Within tolerance: let the clock catch up before issuing
+                    sync::sleep(Duration::from_millis(delta));
+                }
+                Reservation::Exhausted { last_ts, .. } => {
+                    // Sequence exhausted: wait for the next millisecond with exponential backoff
+                    self.wait_next_millis(last_ts, backoff_ms)?;
+                    backoff_ms = (backoff_ms.saturating_mul(2)).min(SnowID::MAX_BACKOFF_MS);
+                }
+                Reservation::Unpersisted => {
+                    // The background write of the high-water mark is behind: give it time
+                    sync::sleep(Duration::from_millis(backoff_ms));
+                    backoff_ms = (backoff_ms.saturating_mul(2)).min(SnowID::MAX_BACKOFF_MS);
                 }
-                // Someone else advanced the timestamp; retry
-                continue;
             }
+        }
+    }
 
-            // Same millisecond: increment sequence atomically and use the returned slot
-            let seq_prev = self.sequence.fetch_add(1, Ordering::AcqRel);
-            if seq_prev < self.config.max_sequence_id() {
-                let seq_to_use = seq_prev + 1;
-                return self.create_snowid(ts, seq_to_use);
+    /// Single non-blocking reservation attempt; only retries on lost CAS races
+    fn try_reserve(&self, wanted: u32) -> Result<Reservation, SnowIDError> {
+        loop {
+            // Read the current time and last issued state
+            let now_ms = self.elapsed_millis();
+            let now = self.config.millis_to_ticks(now_ms);
+            let state = self.state.load(Ordering::Acquire);
+            let (last_ts, seq) = self.unpack_state(state);
+
+            if now < last_ts {
+                self.check_regression(now_ms, last_ts)?;
+                if let ClockRegressionPolicy::Wait { .. } = self.config.clock_regression_policy() {
+                    return Ok(Reservation::Behind {
+                        delta: self.config.ticks_to_millis(last_ts) - now_ms,
+                    });
+                }
             }
-            // Sequence exhausted: wait for the next millisecond with exponential backoff
-            let wait_from = ts;
-            let next_ts = self.wait_next_millis(wait_from, backoff_ms);
-            backoff_ms = (backoff_ms.saturating_mul(2)).min(Self::MAX_BACKOFF_MS);
-
-            // Try to publish the advanced timestamp and reset sequence
-            loop {
-                let current_last = self.last_timestamp.load(Ordering::Acquire);
-                if next_ts <= current_last {
-                    // Another thread already advanced; restart outer loop
-                    break;
+
+            let (ts, first_seq) = if now > last_ts {
+                // New tick: restart the sequence at 0, unless it no longer fits the layout
+                if now > self.config.timestamp_mask() {
+                    return Err(SnowIDError::TimestampOverflow {
+                        ticks: now,
+                        max: self.config.timestamp_mask(),
+                    });
                 }
-                if let Some(id) = self.try_advance_timestamp(current_last, next_ts) {
-                    return id;
+                (now, 0u32)
+            } else if seq < self.config.max_sequence_id() {
+                // Same tick (or clamped under regression): continue after the last slot
+                (last_ts, seq as u32 + 1)
+            } else {
+                return Ok(Reservation::Exhausted {
+                    last_ts,
+                    wait_ms: self.config.ticks_to_millis(last_ts + 1) - now_ms,
+                });
+            };
+
+            if let Some(persistence) = &self.persistence {
+                let issued = self.config.epoch() + self.config.ticks_to_millis(ts);
+                if persistence.record(issued)? == persist::Recorded::Behind {
+                    return Ok(Reservation::Unpersisted);
                 }
-                // Lost the race; retry inner publish or restart
+            }
+
+            let count = wanted.min(self.config.max_sequence_id() as u32 + 1 - first_seq);
+            let next = self.pack_state(ts, (first_seq + count - 1) as u16);
+
+            // Publish timestamp and last claimed sequence together; on a lost race re-read and retry
+            if self
+                .state
+                .compare_exchange_weak(state, next, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+            {
+                return Ok(Reservation::Claimed {
+                    ts,
+                    first_seq: first_seq as u16,
+                    count,
+                });
             }
         }
     }
 
-    /// Get current time in milliseconds since epoch
+    /// Pack a timestamp and sequence into the generator state word
     #[inline(always)]
-    fn get_time_since_epoch(&self) -> u64 {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("System time before Unix epoch!");
+    fn pack_state(&self, timestamp: u64, sequence: u16) -> u64 {
+        (timestamp << self.config.sequence_bits()) | sequence as u64
+    }
 
-        // Convert to milliseconds and subtract the custom epoch
-        // Use wrapping_sub for better codegen (epoch is always < current time)
-        now.as_millis() as u64 - self.config.epoch()
+    /// Split the generator state word into timestamp and sequence
+    #[inline(always)]
+    fn unpack_state(&self, state: u64) -> (u64, u16) {
+        (
+            state >> self.config.sequence_bits(),
+            (state & self.config.sequence_mask() as u64) as u16,
+        )
     }
 
-    /// Wait until next millisecond with an optional micro spin/yield before sleeping.
-    /// The spin reduces latency around the millisecond boundary when sequence overflows.
-    fn wait_next_millis(&self, from_timestamp: u64, mut backoff_ms: u64) -> u64 {
+    /// Apply the clock regression policy to a clock reading (`now_ms` since epoch) behind
+    /// the start of tick `last_ts`
+    ///
+    /// Returns an error if the policy refuses to keep going, `Ok(())` if the
+    /// regression is tolerated.
+    #[cold]
+    fn check_regression(&self, now_ms: u64, last_ts: u64) -> Result<(), SnowIDError> {
+        let delta = self.config.ticks_to_millis(last_ts) - now_ms;
+        match self.config.clock_regression_policy() {
+            ClockRegressionPolicy::Clamp => Ok(()),
+            ClockRegressionPolicy::Wait { tolerance_ms } if delta <= tolerance_ms => Ok(()),
+            ClockRegressionPolicy::Wait { .. } | ClockRegressionPolicy::Fail => {
+                Err(SnowIDError::ClockMovedBackwards {
+                    delta: delta as i64,
+                })
+            }
+        }
+    }
+
+    /// Get current time in milliseconds since the custom epoch
+    #[inline(always)]
+    fn elapsed_millis(&self) -> u64 {
+        // `build()` rejects future epochs, but a custom or stepped-back clock may still read earlier
+        self.clock.now_millis().saturating_sub(self.config.epoch())
+    }
+
+    /// Get current time in ticks since the custom epoch
+    #[inline(always)]
+    fn get_time_since_epoch(&self) -> u64 {
+        self.config.millis_to_ticks(self.elapsed_millis())
+    }
+
+    /// Wait until the next tick with an optional micro spin/yield before sleeping.
+    /// The spin reduces latency around the tick boundary when sequence overflows.
+    fn wait_next_millis(
+        &self,
+        from_timestamp: u64,
+        mut backoff_ms: u64,
+    ) -> Result<u64, SnowIDError> {
         loop {
             // Micro spin/yield to quickly catch the boundary without oversleeping
             if self.config.spin_enabled() && self.config.spin_loops() > 0 {
                 let yield_every = self.config.spin_yield_every();
                 for i in 0..self.config.spin_loops() {
-                    if let Some(new_ts) = self.check_timestamp_advanced(from_timestamp) {
-                        return new_ts;
+                    if let Some(new_ts) = self.check_timestamp_advanced(from_timestamp)? {
+                        return Ok(new_ts);
                     }
-                    std::hint::spin_loop();
+                    sync::spin_loop();
                     if yield_every != 0 && i % yield_every == yield_every - 1 {
-                        thread::yield_now();
+                        sync::yield_now();
                     }
                 }
             }
 
             // Fall back to sleep with exponential backoff under heavy contention
-            thread::sleep(Duration::from_millis(backoff_ms));
-            if let Some(new_ts) = self.check_timestamp_advanced(from_timestamp) {
-                return new_ts;
+            sync::sleep(Duration::from_millis(backoff_ms));
+            if let Some(new_ts) = self.check_timestamp_advanced(from_timestamp)? {
+                return Ok(new_ts);
             }
-            backoff_ms = backoff_ms.saturating_mul(2).min(Self::MAX_BACKOFF_MS);
+            backoff_ms = backoff_ms.saturating_mul(2).min(SnowID::MAX_BACKOFF_MS);
         }
     }
 
-    /// Check if timestamp has advanced beyond the given value
+    /// Check if the current tick has advanced beyond the given value
     #[inline]
-    fn check_timestamp_advanced(&self, from_timestamp: u64) -> Option<u64> {
-        let new_ts = self.get_time_since_epoch();
-        (new_ts > from_timestamp).then_some(new_ts)
-    }
-
-    /// Try to advance timestamp and reset sequence, returning ID if successful
-    #[inline(always)]
-    fn try_advance_timestamp(&self, old_ts: u64, new_ts: u64) -> Option<u64> {
-        // Use compare_exchange_weak for better performance in loops
-        match self.last_timestamp.compare_exchange_weak(
-            old_ts,
-            new_ts,
-            Ordering::AcqRel,
-            Ordering::Acquire,
-        ) {
-            Ok(_) => {
-                self.sequence.store(0, Ordering::Release);
-                Some(self.create_snowid(new_ts, 0))
-            }
-            Err(_) => None,
+    fn check_timestamp_advanced(&self, from_timestamp: u64) -> Result<Option<u64>, SnowIDError> {
+        let now_ms = self.elapsed_millis();
+        let new_ts = self.config.millis_to_ticks(now_ms);
+        if new_ts < from_timestamp {
+            self.check_regression(now_ms, from_timestamp)?;
         }
+        Ok((new_ts > from_timestamp).then_some(new_ts))
     }
 
     #[inline(always)]
@@ -270,9 +623,9 @@ fn create_snowid(&self, timestamp: u64, sequence: u16) -> u64 {
 
     #[inline(always)]
     fn create_snowid_with_node(&self, timestamp: u64, node_id: u16, sequence: u16) -> u64 {
-        // Branchless bit manipulation - masks are compile-time constants
-        // Mask timestamp to ensure it fits in allocated bits
-        ((timestamp & self.config.timestamp_mask()) << self.config.timestamp_shift())
+        // Branchless bit manipulation; reservation guarantees the timestamp fits its field
+        debug_assert!(timestamp <= self.config.timestamp_mask());
+        (timestamp << self.config.timestamp_shift())
             | ((node_id as u64) << self.config.node_shift())
             | (sequence as u64)
     }
@@ -282,8 +635,24 @@ fn create_snowid_with_node(&self, timestamp: u64, node_id: u16, sequence: u16) -
     /// # Returns
     /// * `String` - New base62 encoded SnowID value
     pub fn generate_base62(&self) -> String {
-        let id = self.generate();
-        base62_encode(id)
+        self.generate_base62_str().into()
+    }
+
+    /// Generate a new base62 encoded SnowID without allocating
+    ///
+    /// # Returns
+    /// * `Base62Str` - New base62 encoded SnowID stored inline, dereferencing to `&str`
+    #[inline]
+    pub fn generate_base62_str(&self) -> Base62Str {
+        Base62Str::new(self.generate())
+    }
+
+    /// Generate a new fixed-width base62 encoded SnowID whose string order matches ID order
+    ///
+    /// # Returns
+    /// * `String` - New SnowID as exactly 11 base62 characters, see `base62_encode_sortable`
+    pub fn generate_base62_sortable(&self) -> String {
+        Base62Str::sortable(self.generate()).into()
     }
 
     /// Generate a new base62 encoded SnowID and return both the encoded string and the raw u64 value
@@ -292,7 +661,7 @@ pub fn generate_base62(&self) -> String {
     /// * `(String, u64)` - Tuple containing the base62 encoded SnowID and the raw u64 value
     pub fn generate_base62_with_raw(&self) -> (String, u64) {
         let id = self.generate();
-        (base62_encode(id), id)
+        (Base62Str::new(id).into(), id)
     }
 
     /// Decode a base62 encoded SnowID back to its raw u64 value
@@ -303,7 +672,7 @@ pub fn generate_base62_with_raw(&self) -> (String, u64) {
     /// # Returns
     /// * `Result<u64, Base62DecodeError>` - The decoded u64 SnowID or an error
     pub fn decode_base62(&self, encoded: &str) -> Result<u64, Base62DecodeError> {
-        base62_decode(encoded)
+        self.extract.decode_base62(encoded)
     }
 
     /// Decompose a base62 encoded SnowID into its components: timestamp, node ID, and sequence
@@ -314,8 +683,7 @@ pub fn decode_base62(&self, encoded: &str) -> Result<u64, Base62DecodeError> {
     /// # Returns
     /// * `Result<(u64, u16, u16), Base62DecodeError>` - Tuple containing the components or an error
     pub fn decompose_base62(&self, encoded: &str) -> Result<(u64, u16, u16), Base62DecodeError> {
-        let id = self.decode_base62(encoded)?;
-        Ok(self.extract.decompose(id))
+        self.extract.decompose_base62(encoded)
     }
 }
 
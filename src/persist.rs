@@ -0,0 +1,286 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::SnowIDError;
+
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_SAFETY_MARGIN: Duration = Duration::from_secs(2);
+
+/// Location and timing of the file holding a generator's high-water mark
+///
+/// A background thread records the Unix millisecond of the latest issued timestamp whenever
+/// it has advanced by at least `flush_interval` since the previous write, so generation never
+/// waits for the disk. After a restart, no ID is issued before the clock passes the recorded
+/// mark plus `safety_margin`, so a clock that stepped backwards across the restart cannot
+/// reissue IDs. The safety margin must be at least the flush interval; the headroom between
+/// them is the time the background write has before generation has to wait for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateFile {
+    path: PathBuf,
+    flush_interval: Duration,
+    safety_margin: Duration,
+}
+
+impl StateFile {
+    /// Create a state file description with the default 1s flush interval and 2s safety margin
+    ///
+    /// # Arguments
+    /// * `path` - File the high-water mark is read from and written to
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+            safety_margin: DEFAULT_SAFETY_MARGIN,
+        }
+    }
+
+    /// Set how far the issued timestamp must advance before the mark is written again.
+    /// Shorter intervals mean more writes but a smaller safety margin is needed.
+    pub fn flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = interval;
+        self
+    }
+
+    /// Set how far past the persisted mark the clock must be before issuing after a restart.
+    /// Must be at least the flush interval, which `SnowID::with_state_file` checks.
+    pub fn safety_margin(mut self, margin: Duration) -> Self {
+        self.safety_margin = margin;
+        self
+    }
+
+    /// Path of the state file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read the persisted mark, `None` if the file does not exist yet
+    fn read(&self) -> Result<Option<u64>, SnowIDError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(self.error(err)),
+        };
+        contents.trim().parse().map(Some).map_err(|_| {
+            self.error(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid high-water mark {:?}", contents.trim()),
+            ))
+        })
+    }
+
+    /// Durably replace the persisted mark: write a sibling file, sync it, rename it over the
+    /// old one, then sync the directory so the rename itself survives a crash
+    fn write(&self, mark: u64) -> Result<(), SnowIDError> {
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+
+        let result = File::create(&tmp)
+            .and_then(|mut file| {
+                writeln!(file, "{mark}")?;
+                file.sync_all()
+            })
+            .and_then(|()| fs::rename(&tmp, &self.path))
+            .and_then(|()| self.sync_dir());
+        result.map_err(|err| self.error(err))
+    }
+
+    #[cfg(unix)]
+    fn sync_dir(&self) -> io::Result<()> {
+        let dir = match self.path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        File::open(dir)?.sync_all()
+    }
+
+    /// Directories cannot be opened for syncing on this platform; the rename is as durable
+    /// as the file system makes it
+    #[cfg(not(unix))]
+    fn sync_dir(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn error(&self, err: io::Error) -> SnowIDError {
+        SnowIDError::Persistence {
+            kind: err.kind(),
+            message: format!("{}: {err}", self.path.display()),
+        }
+    }
+}
+
+/// Outcome of recording a timestamp about to be issued
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Recorded {
+    /// The durable mark plus the safety margin covers the timestamp
+    Covered,
+    /// The background write has fallen behind; retry once it catches up
+    Behind,
+}
+
+/// State shared between the generator and its flusher thread
+#[derive(Debug)]
+struct Shared {
+    file: StateFile,
+    /// Latest timestamp the generator asked to issue, in Unix milliseconds
+    pending: AtomicU64,
+    /// Last mark durably written, in Unix milliseconds
+    written: AtomicU64,
+    /// Set when the flusher has been woken and not yet looked at `pending`
+    requested: AtomicBool,
+    stop: AtomicBool,
+    /// Failure of the latest write, reported once generation has to wait for the disk
+    error: Mutex<Option<SnowIDError>>,
+}
+
+impl Shared {
+    /// Flusher loop: write `pending` whenever it is a flush interval past the durable mark
+    fn run(&self) {
+        let interval = self.file.flush_interval.as_millis() as u64;
+        loop {
+            let stop = self.stop.load(Ordering::Acquire);
+            // Acquires the `pending` update of whichever `record` set the flag
+            self.requested.swap(false, Ordering::AcqRel);
+            let pending = self.pending.load(Ordering::Acquire);
+            let written = self.written.load(Ordering::Acquire);
+            // On shutdown, persist whatever is left so the next run starts from it
+            if pending >= written.saturating_add(interval) || (stop && pending > written) {
+                let result = self.file.write(pending);
+                let mut error = self.error.lock().unwrap_or_else(PoisonError::into_inner);
+                match result {
+                    Ok(()) => {
+                        self.written.store(pending, Ordering::Release);
+                        *error = None;
+                    }
+                    Err(err) => *error = Some(err),
+                }
+            }
+            if stop {
+                return;
+            }
+            // Woken early by `record`; the timeout retries failed writes
+            thread::park_timeout(self.file.flush_interval.max(Duration::from_millis(1)));
+        }
+    }
+}
+
+/// Runtime state of a generator's high-water mark file
+#[derive(Debug)]
+pub(crate) struct Persistence {
+    shared: Arc<Shared>,
+    flusher: Option<JoinHandle<()>>,
+}
+
+impl Persistence {
+    /// Open the state file and start its flusher thread, returning the runtime state and the
+    /// Unix millisecond before which no ID may be issued (the persisted mark plus the safety
+    /// margin)
+    ///
+    /// The mark is advanced to `now_unix_ms` (or the floor, if later) before returning, so
+    /// generation can start without waiting for the first background write.
+    pub(crate) fn open(
+        file: StateFile,
+        now_unix_ms: u64,
+    ) -> Result<(Self, Option<u64>), SnowIDError> {
+        if file.safety_margin < file.flush_interval {
+            return Err(SnowIDError::Persistence {
+                kind: io::ErrorKind::InvalidInput,
+                message: format!(
+                    "{}: safety margin {:?} is shorter than the flush interval {:?}",
+                    file.path.display(),
+                    file.safety_margin,
+                    file.flush_interval
+                ),
+            });
+        }
+
+        let mark = file.read()?;
+        let margin = file.safety_margin.as_millis() as u64;
+        let floor = mark.map(|mark| mark.saturating_add(margin));
+        let initial = floor.unwrap_or(0).max(now_unix_ms);
+        file.write(initial)?;
+
+        let shared = Arc::new(Shared {
+            file,
+            pending: AtomicU64::new(initial),
+            written: AtomicU64::new(initial),
+            requested: AtomicBool::new(false),
+            stop: AtomicBool::new(false),
+            error: Mutex::new(None),
+        });
+        let flusher = thread::Builder::new()
+            .name("snowid-state-flusher".into())
+            .spawn({
+                let shared = Arc::clone(&shared);
+                move || shared.run()
+            })
+            .map_err(|err| shared.file.error(err))?;
+
+        let persistence = Self {
+            shared,
+            flusher: Some(flusher),
+        };
+        Ok((persistence, floor))
+    }
+
+    /// Record that IDs are about to be issued at Unix millisecond `issued`
+    ///
+    /// Only touches atomics: the write is handed to the flusher thread once the mark is a
+    /// flush interval old. Returns `Behind` if `issued` is past the durable mark plus the
+    /// safety margin, or the failure of the last write if the flusher could not catch up.
+    #[inline]
+    pub(crate) fn record(&self, issued: u64) -> Result<Recorded, SnowIDError> {
+        let shared = &*self.shared;
+        let interval = shared.file.flush_interval.as_millis() as u64;
+        let margin = shared.file.safety_margin.as_millis() as u64;
+
+        // Published before issuing, so the mark may run ahead of the IDs but never behind them
+        shared.pending.fetch_max(issued, Ordering::AcqRel);
+        let written = shared.written.load(Ordering::Acquire);
+        if issued >= written.saturating_add(interval) {
+            self.request_flush();
+        }
+        if issued < written.saturating_add(margin) {
+            return Ok(Recorded::Covered);
+        }
+        self.behind()
+    }
+
+    #[cold]
+    fn request_flush(&self) {
+        if !self.shared.requested.swap(true, Ordering::AcqRel)
+            && let Some(flusher) = &self.flusher
+        {
+            flusher.thread().unpark();
+        }
+    }
+
+    #[cold]
+    fn behind(&self) -> Result<Recorded, SnowIDError> {
+        let error = self
+            .shared
+            .error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match &*error {
+            Some(err) => Err(err.clone()),
+            None => Ok(Recorded::Behind),
+        }
+    }
+}
+
+impl Drop for Persistence {
+    /// Stop the flusher after a final write of the latest mark
+    fn drop(&mut self) {
+        self.shared.stop.store(true, Ordering::Release);
+        if let Some(flusher) = self.flusher.take() {
+            flusher.thread().unpark();
+            let _ = flusher.join();
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use thiserror::Error;
+
+use crate::{Base62DecodeError, Clock, Id, SnowIDError, TypedSnowID};
+
+/// Separator between the entity prefix and the base62 value; never part of the base62 alphabet
+const SEPARATOR: char = '_';
+
+/// Registers the string prefix of an entity's IDs, e.g. `usr` for `usr_2qPfVQh7Jw9`
+///
+/// Implement it on the marker types used with [`Id<T>`] to get self-describing IDs from
+/// [`Id::to_prefixed`] and [`TypedSnowID::generate_prefixed`], and to have
+/// [`Id::parse_prefixed`] reject IDs of other entities. Prefixes should be short, ASCII
+/// and unique across entities.
+pub trait Prefixed {
+    /// Prefix written before the separator, without the trailing `_`
+    const PREFIX: &'static str;
+}
+
+/// Error returned when parsing a prefixed ID
+#[derive(Debug, Error)]
+pub enum PrefixedIdError {
+    /// The input has no `_` separating a prefix from the value
+    #[error("Missing `{expected}_` prefix in ID {input:?}")]
+    MissingPrefix {
+        expected: &'static str,
+        input: String,
+    },
+    /// The prefix belongs to a different entity type
+    #[error("Expected a `{expected}_` ID but found prefix `{found}_`")]
+    PrefixMismatch {
+        expected: &'static str,
+        found: String,
+    },
+    /// The part after the prefix is not valid base62
+    #[error("Invalid value in `{expected}_` ID: {source}")]
+    Base62 {
+        expected: &'static str,
+        source: Base62DecodeError,
+    },
+}
+
+impl<T: Prefixed> Id<T> {
+    /// Prefixed base62 representation, e.g. `usr_2qPfVQh7Jw9`
+    pub fn to_prefixed(&self) -> String {
+        format!("{}{SEPARATOR}{}", T::PREFIX, self.to_base62())
+    }
+
+    /// Parse a prefixed ID, checking that its prefix is `T::PREFIX`
+    ///
+    /// # Returns
+    /// * `Result<Id<T>, PrefixedIdError>` - The ID, or which part of the input was wrong
+    pub fn parse_prefixed(input: &str) -> Result<Self, PrefixedIdError> {
+        let expected = T::PREFIX;
+        // The base62 alphabet has no separator, so the last one ends the prefix
+        let Some((prefix, encoded)) = input.rsplit_once(SEPARATOR) else {
+            return Err(PrefixedIdError::MissingPrefix {
+                expected,
+                input: input.to_owned(),
+            });
+        };
+        if prefix != expected {
+            return Err(PrefixedIdError::PrefixMismatch {
+                expected,
+                found: prefix.to_owned(),
+            });
+        }
+        Self::from_base62(encoded).map_err(|source| PrefixedIdError::Base62 { expected, source })
+    }
+}
+
+impl<T: Prefixed, C: Clock> TypedSnowID<'_, T, C> {
+    /// Generate a new prefixed ID for a `T`, e.g. `usr_2qPfVQh7Jw9`
+    ///
+    /// # Panics
+    /// Panics under the same conditions as [`SnowID::generate`](crate::SnowID::generate).
+    pub fn generate_prefixed(&self) -> String {
+        self.generate().to_prefixed()
+    }
+
+    /// Generate a new prefixed ID for a `T`, reporting errors instead of panicking
+    pub fn try_generate_prefixed(&self) -> Result<String, SnowIDError> {
+        Ok(self.try_generate()?.to_prefixed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SnowID, base62_encode};
+
+    struct User;
+    struct Order;
+    struct Secret;
+
+    impl Prefixed for User {
+        const PREFIX: &'static str = "usr";
+    }
+
+    impl Prefixed for Order {
+        const PREFIX: &'static str = "ord";
+    }
+
+    impl Prefixed for Secret {
+        const PREFIX: &'static str = "sk_live";
+    }
+
+    #[test]
+    fn test_prefixed_roundtrip() {
+        let generator = SnowID::new(1).unwrap();
+        let id = generator.typed::<User>().generate();
+        let encoded = id.to_prefixed();
+
+        assert_eq!(encoded, format!("usr_{}", base62_encode(id.as_u64())));
+        assert_eq!(Id::<User>::parse_prefixed(&encoded).unwrap(), id);
+
+        let encoded = generator.typed::<Secret>().generate_prefixed();
+        assert!(encoded.starts_with("sk_live_"));
+        assert!(Id::<Secret>::parse_prefixed(&encoded).is_ok());
+    }
+
+    #[test]
+    fn test_prefix_mismatch() {
+        let generator = SnowID::new(1).unwrap();
+        let order = generator.typed::<Order>().try_generate_prefixed().unwrap();
+
+        let err = Id::<User>::parse_prefixed(&order).unwrap_err();
+        assert!(matches!(
+            &err,
+            PrefixedIdError::PrefixMismatch { expected: "usr", found } if found == "ord"
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Expected a `usr_` ID but found prefix `ord_`"
+        );
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert!(matches!(
+            Id::<User>::parse_prefixed("2qPfVQh7Jw9"),
+            Err(PrefixedIdError::MissingPrefix {
+                expected: "usr",
+                ..
+            })
+        ));
+        assert!(matches!(
+            Id::<User>::parse_prefixed("usr_"),
+            Err(PrefixedIdError::Base62 { .. })
+        ));
+        assert!(matches!(
+            Id::<User>::parse_prefixed("usr_not-base62"),
+            Err(PrefixedIdError::Base62 { .. })
+        ));
+        assert!(matches!(
+            Id::<User>::parse_prefixed("usr_2qPfVQh7Jw9x"),
+            Err(PrefixedIdError::Base62 {
+                source: Base62DecodeError::InvalidInput,
+                ..
+            })
+        ));
+    }
+}
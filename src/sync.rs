@@ -0,0 +1,24 @@
+//! Concurrency primitives used by the generator.
+//!
+//! Under `--cfg loom` these resolve to loom's model-checked equivalents so the
+//! lock-free state machine can be verified across all thread interleavings.
+
+#[cfg(loom)]
+pub(crate) use loom::hint::spin_loop;
+#[cfg(loom)]
+pub(crate) use loom::sync::atomic::{AtomicU64, Ordering};
+#[cfg(loom)]
+pub(crate) use loom::thread::yield_now;
+
+#[cfg(not(loom))]
+pub(crate) use std::hint::spin_loop;
+#[cfg(not(loom))]
+pub(crate) use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(not(loom))]
+pub(crate) use std::thread::{sleep, yield_now};
+
+/// Loom has no notion of time, so sleeping degrades to a yield
+#[cfg(loom)]
+pub(crate) fn sleep(_duration: std::time::Duration) {
+    loom::thread::yield_now();
+}
@@ -107,4 +107,39 @@ fn test_base62_input_length_validation() {
             panic!("Should accept 11 character input");
         }
     }
+
+    #[test]
+    fn test_base62_sortable_generation() {
+        let generator = SnowID::new(1).unwrap();
+
+        let encoded: Vec<String> = (0..1000)
+            .map(|_| generator.generate_base62_sortable())
+            .collect();
+        assert!(encoded.iter().all(|id| id.len() == BASE62_SORTABLE_LEN));
+
+        // Generation order, string order and numeric order all agree
+        let mut sorted = encoded.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, encoded);
+        let decoded: Vec<u64> = encoded
+            .iter()
+            .map(|id| base62_decode_sortable(id).unwrap())
+            .collect();
+        assert!(decoded.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(generator.extract.node(decoded[0]), 1);
+    }
+
+    #[test]
+    fn test_base62_str_generation() {
+        let generator = SnowID::new(1).unwrap();
+
+        let encoded = generator.generate_base62_str();
+        let id = generator.decode_base62(&encoded).unwrap();
+        assert_eq!(encoded.as_str(), base62_encode(id));
+        assert_eq!(generator.extract.node(id), 1);
+
+        // The allocating variants produce the same encoding
+        let (encoded, raw) = generator.generate_base62_with_raw();
+        assert_eq!(Base62Str::new(raw), encoded.as_str());
+    }
 }
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use crate::tests::{EPOCH, ManualClock, tight_config};
+    use crate::*;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    fn assert_strictly_increasing(ids: &[u64]) {
+        for pair in ids.windows(2) {
+            assert!(
+                pair[1] > pair[0],
+                "{} is not greater than {}",
+                pair[1],
+                pair[0]
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_batch_unique_and_ordered() {
+        let generator = SnowID::new(1).unwrap();
+        let ids = generator.generate_batch(20_000);
+
+        assert_eq!(ids.len(), 20_000);
+        assert_strictly_increasing(&ids);
+        assert!(ids.iter().all(|&id| generator.extract.node(id) == 1));
+    }
+
+    #[test]
+    fn test_generate_batch_empty() {
+        let generator = SnowID::new(1).unwrap();
+        assert!(generator.generate_batch(0).is_empty());
+    }
+
+    #[test]
+    fn test_batch_claims_contiguous_block() {
+        let clock = ManualClock::new(EPOCH + 1_000);
+        let generator = SnowID::with_clock(1, SnowIDConfig::default(), clock).unwrap();
+
+        let mut ids = [0u64; 100];
+        generator.generate_into(&mut ids);
+        for (expected_seq, &id) in ids.iter().enumerate() {
+            assert_eq!(generator.extract.timestamp(id), 1_000);
+            assert_eq!(generator.extract.sequence(id) as usize, expected_seq);
+        }
+
+        // Single IDs and batches share the same state
+        let next = generator.generate();
+        assert_eq!(generator.extract.sequence(next), 100);
+    }
+
+    #[test]
+    fn test_batch_spills_into_next_millisecond() {
+        let cfg = tight_config().build().unwrap();
+        let clock = Arc::new(ManualClock::new(EPOCH + 1_000));
+        let generator = Arc::new(SnowID::with_clock(1, cfg, Arc::clone(&clock)).unwrap());
+
+        let worker = {
+            let generator = Arc::clone(&generator);
+            thread::spawn(move || generator.generate_batch(100))
+        };
+        thread::sleep(Duration::from_millis(20));
+        clock.advance(1);
+
+        let ids = worker.join().unwrap();
+        assert_strictly_increasing(&ids);
+        let (first_ms, second_ms): (Vec<u64>, Vec<u64>) = ids
+            .iter()
+            .partition(|&&id| generator.extract.timestamp(id) == 1_000);
+        assert_eq!(first_ms.len(), 64);
+        assert_eq!(second_ms.len(), 36);
+        assert_eq!(generator.extract.sequence(second_ms[0]), 0);
+    }
+
+    #[test]
+    fn test_concurrent_batches_unique() {
+        let generator = Arc::new(SnowID::new(3).unwrap());
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let generator = Arc::clone(&generator);
+                thread::spawn(move || {
+                    let mut ids = Vec::new();
+                    for _ in 0..10 {
+                        ids.extend(generator.generate_batch(500));
+                        ids.push(generator.generate());
+                    }
+                    ids
+                })
+            })
+            .collect();
+
+        let mut all = HashSet::new();
+        for handle in handles {
+            let ids = handle.join().unwrap();
+            assert_strictly_increasing(&ids);
+            for id in ids {
+                assert!(all.insert(id), "Duplicate ID generated: {id}");
+            }
+        }
+        assert_eq!(all.len(), 4 * 10 * 501);
+    }
+
+    #[test]
+    fn test_try_generate_into_reports_regression() {
+        let cfg = tight_config()
+            .clock_regression_policy(ClockRegressionPolicy::Fail)
+            .build()
+            .unwrap();
+        let generator = SnowID::with_clock(1, cfg, ManualClock::new(EPOCH + 1_000)).unwrap();
+
+        // First 64 IDs fit the current millisecond, then the clock steps back
+        let mut ids = [0u64; 100];
+        generator.try_generate_into(&mut ids[..64]).unwrap();
+        generator.clock().set(EPOCH + 900);
+        assert_eq!(
+            generator.try_generate_into(&mut ids[64..]),
+            Err(SnowIDError::ClockMovedBackwards { delta: 100 })
+        );
+        assert_strictly_increasing(&ids[..64]);
+    }
+}
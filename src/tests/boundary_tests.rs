@@ -36,7 +36,8 @@ fn test_component_boundaries() {
             .node_bits(10)
             .unwrap()
             .epoch(0)
-            .build();
+            .build()
+            .unwrap();
 
         let generator = SnowID::with_config(1023, config).unwrap();
 
@@ -59,7 +60,8 @@ fn test_component_boundaries() {
             .node_bits(12)
             .unwrap()
             .epoch(0)
-            .build();
+            .build()
+            .unwrap();
 
         let custom_gen = SnowID::with_config(4095, custom_config).unwrap();
         let snowid = custom_gen.generate();
@@ -87,4 +89,51 @@ fn test_component_max_values() {
         assert!(node <= generator.config.max_node_id());
         assert!(sequence <= generator.config.max_sequence_id());
     }
+
+    #[test]
+    fn test_timestamp_overflow_is_reported() {
+        use crate::tests::ManualClock;
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+        // 20 bits of milliseconds last about 17 minutes from an epoch set just now
+        let epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64
+            - 1_000;
+        let config = SnowIDConfig::builder()
+            .epoch(epoch)
+            .timestamp_bits(20)
+            .unwrap()
+            .sequence_bits(2)
+            .unwrap()
+            .build()
+            .unwrap();
+        let max = config.timestamp_mask();
+        let generator = SnowID::with_clock(1, config, ManualClock::new(epoch + max)).unwrap();
+        assert_eq!(generator.remaining_lifetime(), Duration::from_millis(1));
+
+        // The last tick still issues all of its sequence slots
+        let ids = generator.generate_batch(4);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(generator.extract.ticks(ids[3]), max);
+
+        assert_eq!(
+            generator.try_generate_now(),
+            Err(SnowIDError::SequenceExhausted {
+                retry_after: Duration::from_millis(1)
+            })
+        );
+
+        generator.clock().advance(1);
+        assert_eq!(generator.remaining_lifetime(), Duration::ZERO);
+        assert_eq!(
+            generator.try_generate(),
+            Err(SnowIDError::TimestampOverflow {
+                ticks: max + 1,
+                max
+            })
+        );
+        assert!(generator.try_generate_into(&mut [0; 2]).is_err());
+    }
 }
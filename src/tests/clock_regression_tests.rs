@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use crate::tests::{EPOCH, ManualClock, generator, tight_config};
+    use crate::*;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    fn exhaust_current_millis<C: Clock>(generator: &SnowID<C>) {
+        for _ in 0..=generator.config.max_sequence_id() {
+            generator.try_generate().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_fail_policy_reports_delta() {
+        let generator = generator(ClockRegressionPolicy::Fail);
+        exhaust_current_millis(&generator);
+
+        generator.clock().set(EPOCH + 4_000);
+        assert_eq!(
+            generator.try_generate(),
+            Err(SnowIDError::ClockMovedBackwards { delta: 1_000 })
+        );
+
+        // Recovers once the clock catches up
+        generator.clock().set(EPOCH + 5_001);
+        let id = generator.try_generate().unwrap();
+        assert_eq!(generator.extract.timestamp(id), 5_001);
+    }
+
+    #[test]
+    fn test_wait_policy_waits_within_tolerance() {
+        let generator = Arc::new(generator(ClockRegressionPolicy::Wait { tolerance_ms: 10 }));
+        exhaust_current_millis(&generator);
+        generator.clock().set(EPOCH + 4_995);
+
+        let worker = {
+            let generator = Arc::clone(&generator);
+            thread::spawn(move || generator.try_generate())
+        };
+        thread::sleep(Duration::from_millis(20));
+        generator.clock().set(EPOCH + 5_001);
+
+        let id = worker.join().unwrap().unwrap();
+        assert_eq!(generator.extract.timestamp(id), 5_001);
+        assert_eq!(generator.extract.sequence(id), 0);
+    }
+
+    #[test]
+    fn test_wait_policy_fails_beyond_tolerance() {
+        let generator = generator(ClockRegressionPolicy::Wait { tolerance_ms: 10 });
+        exhaust_current_millis(&generator);
+
+        generator.clock().set(EPOCH + 4_000);
+        assert_eq!(
+            generator.try_generate(),
+            Err(SnowIDError::ClockMovedBackwards { delta: 1_000 })
+        );
+    }
+
+    #[test]
+    fn test_clamp_policy_never_fails() {
+        let generator = generator(ClockRegressionPolicy::Clamp);
+        exhaust_current_millis(&generator);
+
+        generator.clock().set(EPOCH + 4_000);
+        let worker = {
+            let clock = Arc::clone(generator.clock());
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(20));
+                clock.set(EPOCH + 5_001);
+            })
+        };
+
+        // Blocks on the exhausted millisecond until the clock passes it again
+        let id = generator.try_generate().unwrap();
+        worker.join().unwrap();
+        assert_eq!(generator.extract.timestamp(id), 5_001);
+    }
+
+    #[test]
+    fn test_stale_timestamps_still_detect_regression() {
+        for policy in [
+            ClockRegressionPolicy::Fail,
+            ClockRegressionPolicy::Wait { tolerance_ms: 10 },
+        ] {
+            let cfg = tight_config()
+                .fresh_timestamps(false)
+                .clock_regression_policy(policy)
+                .build()
+                .unwrap();
+            let clock = Arc::new(ManualClock::new(EPOCH + 5_000));
+            let generator = SnowID::with_clock(1, cfg, clock).unwrap();
+            generator.try_generate().unwrap();
+
+            // Free sequence slots remain, so only the fast path's clock check can catch this
+            generator.clock().set(EPOCH + 4_000);
+            assert_eq!(
+                generator.try_generate(),
+                Err(SnowIDError::ClockMovedBackwards { delta: 1_000 })
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Clock moved backwards")]
+    fn test_generate_panics_when_policy_fails() {
+        let generator = generator(ClockRegressionPolicy::Fail);
+        exhaust_current_millis(&generator);
+
+        generator.clock().set(EPOCH + 4_000);
+        generator.generate();
+    }
+}
@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod tests {
+    use crate::tests::{EPOCH, ManualClock, tight_config};
+    use crate::*;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    fn generator_at(millis: u64) -> SnowID<Arc<ManualClock>> {
+        let clock = Arc::new(ManualClock::new(EPOCH + millis));
+        SnowID::with_clock(1, SnowIDConfig::default(), clock).unwrap()
+    }
+
+    #[test]
+    fn test_generate_reads_injected_clock() {
+        let generator = generator_at(1_000);
+        let id = generator.generate();
+        assert_eq!(generator.extract.timestamp(id), 1_000);
+        assert_eq!(generator.extract.sequence(id), 0);
+    }
+
+    #[test]
+    fn test_clock_advance_restamps_ids() {
+        let generator = generator_at(1_000);
+        let first = generator.generate();
+        let second = generator.generate();
+        assert_eq!(generator.extract.sequence(second), 1);
+
+        generator.clock().advance(5);
+        let third = generator.generate();
+        assert!(third > second && second > first);
+        assert_eq!(generator.extract.timestamp(third), 1_005);
+        assert_eq!(generator.extract.sequence(third), 0);
+    }
+
+    #[test]
+    fn test_stale_timestamps_without_freshness() {
+        let cfg = SnowIDConfig::builder()
+            .fresh_timestamps(false)
+            .build()
+            .unwrap();
+        let clock = Arc::new(ManualClock::new(EPOCH + 1_000));
+        let generator = SnowID::with_clock(1, cfg, clock).unwrap();
+        generator.generate();
+
+        // Fast path keeps the cached millisecond until the sequence is exhausted
+        generator.clock().advance(5_000);
+        let id = generator.generate();
+        assert_eq!(generator.extract.timestamp(id), 1_000);
+        assert_eq!(generator.extract.sequence(id), 1);
+    }
+
+    #[test]
+    fn test_clock_jump_backwards_is_clamped() {
+        let generator = generator_at(5_000);
+        let before = generator.generate();
+
+        generator.clock().set(EPOCH + 1_000);
+        let after = generator.generate();
+
+        assert!(after > before);
+        assert_eq!(generator.extract.timestamp(after), 5_000);
+    }
+
+    #[test]
+    fn test_sequence_exhaustion_waits_for_clock() {
+        let cfg = tight_config().build().unwrap();
+        let clock = Arc::new(ManualClock::new(EPOCH + 10));
+        let generator = Arc::new(SnowID::with_clock(1, cfg, Arc::clone(&clock)).unwrap());
+
+        // Use up every slot of the current millisecond
+        for _ in 0..=cfg.max_sequence_id() {
+            assert_eq!(generator.extract.timestamp(generator.generate()), 10);
+        }
+
+        let waiter = {
+            let generator = Arc::clone(&generator);
+            thread::spawn(move || generator.generate())
+        };
+        thread::sleep(Duration::from_millis(20));
+        clock.advance(1);
+
+        let id = waiter.join().unwrap();
+        assert_eq!(generator.extract.timestamp(id), 11);
+        assert_eq!(generator.extract.sequence(id), 0);
+    }
+
+    #[test]
+    fn test_wait_next_millis_uses_clock() {
+        let generator = generator_at(100);
+        generator.clock().advance(3);
+        assert_eq!(generator.wait_next_millis(100, 1).unwrap(), 103);
+    }
+
+    #[test]
+    fn test_coarse_tick_timestamps() {
+        let cfg = SnowIDConfig::builder()
+            .tick(Duration::from_millis(10))
+            .unwrap()
+            .build()
+            .unwrap();
+        let clock = ManualClock::new(EPOCH + 1_005);
+        let generator = SnowID::with_clock(1, cfg, clock).unwrap();
+
+        let first = generator.generate();
+        assert_eq!(generator.extract.ticks(first), 100);
+        assert_eq!(generator.extract.timestamp(first), 1_000);
+
+        // Same tick until the clock crosses the next 10ms boundary
+        generator.clock().advance(4);
+        let second = generator.generate();
+        assert_eq!(generator.extract.ticks(second), 100);
+        assert_eq!(generator.extract.sequence(second), 1);
+
+        generator.clock().advance(1);
+        let third = generator.generate();
+        assert_eq!(generator.extract.timestamp(third), 1_010);
+        assert_eq!(generator.extract.sequence(third), 0);
+    }
+
+    #[test]
+    fn test_coarse_tick_exhaustion_waits_for_next_tick() {
+        let cfg = tight_config()
+            .tick(Duration::from_secs(1))
+            .unwrap()
+            .build()
+            .unwrap();
+        let generator = SnowID::with_clock(1, cfg, ManualClock::new(EPOCH + 2_250)).unwrap();
+        generator.generate_batch(64);
+
+        assert_eq!(
+            generator.try_generate_now(),
+            Err(SnowIDError::SequenceExhausted {
+                retry_after: Duration::from_millis(750)
+            })
+        );
+
+        generator.clock().advance(750);
+        let id = generator.try_generate_now().unwrap();
+        assert_eq!(generator.extract.timestamp(id), 3_000);
+    }
+
+    #[test]
+    fn test_coarse_tick_regression_delta_in_millis() {
+        let cfg = SnowIDConfig::builder()
+            .tick(Duration::from_millis(10))
+            .unwrap()
+            .clock_regression_policy(ClockRegressionPolicy::Fail)
+            .build()
+            .unwrap();
+        let generator = SnowID::with_clock(1, cfg, ManualClock::new(EPOCH + 1_000)).unwrap();
+        generator.generate();
+
+        // Tick 100 starts at 1_000ms; the clock is now 25ms before it
+        generator.clock().set(EPOCH + 975);
+        assert_eq!(
+            generator.try_generate(),
+            Err(SnowIDError::ClockMovedBackwards { delta: 25 })
+        );
+    }
+
+    #[test]
+    fn test_clock_before_epoch_saturates() {
+        let generator =
+            SnowID::with_clock(1, SnowIDConfig::default(), ManualClock::new(EPOCH - 5_000))
+                .unwrap();
+        let id = generator.generate();
+        assert_eq!(generator.extract.timestamp(id), 0);
+        assert!(generator.generate() > id);
+    }
+}
@@ -8,7 +8,8 @@ fn test_clock_backwards() {
         let snowid1 = generator.generate();
 
         // Simulate clock moving backwards by saving current timestamp
-        let original_timestamp = generator.last_timestamp.load(Ordering::SeqCst);
+        let (original_timestamp, _) =
+            generator.unpack_state(generator.state.load(Ordering::SeqCst));
 
         // Generate another ID - it should handle backwards clock gracefully
         let snowid2 = generator.generate();
@@ -24,7 +24,11 @@ fn test_snowid_generation_and_extraction() {
 
     #[test]
     fn test_custom_configuration() {
-        let config = SnowIDConfig::builder().node_bits(12).unwrap().build();
+        let config = SnowIDConfig::builder()
+            .node_bits(12)
+            .unwrap()
+            .build()
+            .unwrap();
 
         let generator = SnowID::with_config(1023, config).unwrap();
 
@@ -65,7 +69,7 @@ fn test_unique_ids_across_nodes() {
     #[test]
     fn test_epoch_handling() {
         let custom_epoch = 1577836800000; // 2020-01-01 00:00:00 UTC
-        let config = SnowIDConfig::builder().epoch(custom_epoch).build();
+        let config = SnowIDConfig::builder().epoch(custom_epoch).build().unwrap();
 
         let generator = SnowID::with_config(1, config).unwrap();
         let snowid = generator.generate();
@@ -79,4 +83,37 @@ fn test_epoch_handling() {
         assert!(unix_ts > custom_epoch);
         assert!(unix_ts < (custom_epoch + (1u64 << 41))); // Should be within ~69 years of epoch
     }
+
+    #[test]
+    fn test_custom_layout_roundtrip() {
+        let config = SnowIDConfig::builder()
+            .timestamp_bits(41)
+            .unwrap()
+            .node_bits(13)
+            .unwrap()
+            .sequence_bits(10)
+            .unwrap()
+            .build()
+            .unwrap();
+        let generator = SnowID::with_config(8191, config).unwrap();
+        let id = generator.generate();
+        assert_eq!(generator.extract.node(id), 8191);
+        assert_eq!(generator.extract.sequence(id), 0);
+        assert_eq!(id >> 23, generator.extract.ticks(id));
+        assert!(SnowID::with_config(8192, config).is_err());
+    }
+
+    #[test]
+    fn test_reserved_sign_bit_ids_are_non_negative() {
+        let config = SnowIDConfig::builder()
+            .reserve_sign_bit(true)
+            .build()
+            .unwrap();
+        let generator = SnowID::with_config(1023, config).unwrap();
+        for id in generator.generate_batch(5000) {
+            assert!((id as i64) >= 0);
+            assert_eq!(id >> 63, 0);
+            assert_eq!(generator.extract.node(id), 1023);
+        }
+    }
 }
@@ -0,0 +1,120 @@
+//! Model-checked tests for the packed generator state.
+//!
+//! Run with:
+//! `RUSTFLAGS="--cfg loom" cargo test --release --lib loom_tests`
+
+#[cfg(all(test, loom))]
+mod tests {
+    use crate::tests::EPOCH;
+    use crate::*;
+    use loom::sync::Arc;
+    use loom::sync::atomic::{AtomicU64, Ordering};
+    use loom::thread;
+    use std::collections::HashSet;
+
+    /// Clock frozen at a single millisecond
+    struct FixedClock(u64);
+
+    impl Clock for FixedClock {
+        fn now_millis(&self) -> u64 {
+            self.0
+        }
+    }
+
+    /// Clock that advances by one millisecond on every read, forcing timestamp
+    /// transitions to race with sequence increments
+    struct TickingClock(AtomicU64);
+
+    impl Clock for TickingClock {
+        fn now_millis(&self) -> u64 {
+            self.0.fetch_add(1, Ordering::SeqCst)
+        }
+    }
+
+    fn assert_unique_and_well_formed<C: Clock>(generator: &SnowID<C>, ids: &[u64]) {
+        let unique: HashSet<_> = ids.iter().collect();
+        assert_eq!(unique.len(), ids.len(), "duplicate IDs: {ids:?}");
+        for &id in ids {
+            assert_eq!(generator.extract.node(id), generator.node_id);
+        }
+    }
+
+    fn run_concurrently<C>(generator: Arc<SnowID<C>>, threads: usize, per_thread: usize) -> Vec<u64>
+    where
+        C: Clock + Send + Sync + 'static,
+    {
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                let generator = Arc::clone(&generator);
+                thread::spawn(move || {
+                    // IDs from one thread must be strictly increasing
+                    let ids: Vec<u64> = (0..per_thread).map(|_| generator.generate()).collect();
+                    assert!(
+                        ids.windows(2).all(|w| w[0] < w[1]),
+                        "not monotonic: {ids:?}"
+                    );
+                    ids
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn loom_no_duplicates_same_millisecond() {
+        loom::model(|| {
+            let clock = FixedClock(EPOCH + 1_000);
+            let generator =
+                Arc::new(SnowID::with_clock(3, SnowIDConfig::default(), clock).unwrap());
+
+            let ids = run_concurrently(Arc::clone(&generator), 2, 2);
+            assert_unique_and_well_formed(&generator, &ids);
+            for &id in &ids {
+                assert_eq!(generator.extract.timestamp(id), 1_000);
+            }
+        });
+    }
+
+    #[test]
+    fn loom_no_duplicates_across_millisecond_transitions() {
+        loom::model(|| {
+            let clock = TickingClock(AtomicU64::new(EPOCH + 1_000));
+            let generator =
+                Arc::new(SnowID::with_clock(3, SnowIDConfig::default(), clock).unwrap());
+
+            let ids = run_concurrently(Arc::clone(&generator), 2, 2);
+            assert_unique_and_well_formed(&generator, &ids);
+        });
+    }
+
+    #[test]
+    fn loom_no_duplicates_without_fresh_timestamps() {
+        loom::model(|| {
+            let cfg = SnowIDConfig::builder()
+                .fresh_timestamps(false)
+                .build()
+                .unwrap();
+            let clock = TickingClock(AtomicU64::new(EPOCH + 1_000));
+            let generator = Arc::new(SnowID::with_clock(3, cfg, clock).unwrap());
+
+            let ids = run_concurrently(Arc::clone(&generator), 2, 2);
+            assert_unique_and_well_formed(&generator, &ids);
+        });
+    }
+
+    #[test]
+    fn loom_no_duplicates_three_threads() {
+        loom::model(|| {
+            let clock = FixedClock(EPOCH + 1_000);
+            let generator =
+                Arc::new(SnowID::with_clock(3, SnowIDConfig::default(), clock).unwrap());
+
+            let ids = run_concurrently(Arc::clone(&generator), 3, 1);
+            assert_unique_and_well_formed(&generator, &ids);
+        });
+    }
+}
@@ -1,7 +1,65 @@
 mod base62_tests;
+mod batch_tests;
 mod boundary_tests;
+mod clock_regression_tests;
+mod clock_tests;
 mod concurrent_tests;
 mod core_tests;
 mod extraction_tests;
+mod loom_tests;
+mod node_parts_tests;
+mod nonblocking_tests;
+mod persistence_tests;
+mod range_tests;
 mod sequence_tests;
 mod timing_tests;
+
+use crate::{Clock, ClockRegressionPolicy, SnowID, SnowIDConfig, SnowIDConfigBuilder};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Default custom epoch (January 1, 2024 UTC); test clocks are set relative to it
+pub(crate) const EPOCH: u64 = 1704067200000;
+
+/// Layout with 64 sequence slots per millisecond so exhaustion is cheap
+pub(crate) fn tight_config() -> SnowIDConfigBuilder {
+    SnowIDConfig::builder().node_bits(16).unwrap()
+}
+
+/// Generator on `tight_config`, on a manual clock 5s past the epoch
+pub(crate) fn generator(policy: ClockRegressionPolicy) -> SnowID<Arc<ManualClock>> {
+    let cfg = tight_config()
+        .clock_regression_policy(policy)
+        .build()
+        .unwrap();
+    let clock = Arc::new(ManualClock::new(EPOCH + 5_000));
+    SnowID::with_clock(1, cfg, clock).unwrap()
+}
+
+/// Manually driven clock for deterministic generator tests
+#[derive(Debug)]
+pub(crate) struct ManualClock {
+    millis: AtomicU64,
+}
+
+impl ManualClock {
+    pub(crate) fn new(millis: u64) -> Self {
+        Self {
+            millis: AtomicU64::new(millis),
+        }
+    }
+
+    pub(crate) fn set(&self, millis: u64) {
+        self.millis.store(millis, Ordering::SeqCst);
+    }
+
+    pub(crate) fn advance(&self, millis: u64) {
+        self.millis.fetch_add(millis, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_millis(&self) -> u64 {
+        self.millis.load(Ordering::SeqCst)
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_with_parts_default_split() {
+        let generator = SnowID::with_parts(3, 17).unwrap();
+        assert_eq!(generator.node_id, (3 << 5) | 17);
+
+        let id = generator.generate();
+        assert_eq!(generator.extract.datacenter(id), 3);
+        assert_eq!(generator.extract.worker(id), 17);
+        assert_eq!(generator.extract.node(id), generator.node_id);
+    }
+
+    #[test]
+    fn test_with_parts_and_config() {
+        let config = SnowIDConfig::builder()
+            .node_bits(14)
+            .unwrap()
+            .sequence_bits(8)
+            .unwrap()
+            .datacenter_bits(4)
+            .unwrap()
+            .build()
+            .unwrap();
+        let generator = SnowID::with_parts_and_config(15, 1023, config).unwrap();
+
+        for id in generator.generate_batch(300) {
+            assert_eq!(generator.extract.datacenter(id), 15);
+            assert_eq!(generator.extract.worker(id), 1023);
+        }
+    }
+
+    #[test]
+    fn test_with_parts_rejects_out_of_range_parts() {
+        assert_eq!(
+            SnowID::with_parts(32, 0).unwrap_err(),
+            SnowIDError::InvalidDatacenterId {
+                datacenter: 32,
+                max: 31
+            }
+        );
+        assert_eq!(
+            SnowID::with_parts(0, 32).unwrap_err(),
+            SnowIDError::InvalidWorkerId {
+                worker: 32,
+                max: 31
+            }
+        );
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use crate::tests::{EPOCH, generator};
+    use crate::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_try_generate_now_reports_exhaustion() {
+        let generator = generator(ClockRegressionPolicy::Clamp);
+        for expected_seq in 0..=generator.config.max_sequence_id() {
+            let id = generator.try_generate_now().unwrap();
+            assert_eq!(generator.extract.sequence(id), expected_seq);
+        }
+
+        assert_eq!(
+            generator.try_generate_now(),
+            Err(SnowIDError::SequenceExhausted {
+                retry_after: Duration::from_millis(1)
+            })
+        );
+
+        generator.clock().advance(1);
+        let id = generator.try_generate_now().unwrap();
+        assert_eq!(generator.extract.timestamp(id), 5_001);
+        assert_eq!(generator.extract.sequence(id), 0);
+    }
+
+    #[test]
+    fn test_try_generate_now_retry_after_under_clamped_regression() {
+        let generator = generator(ClockRegressionPolicy::Clamp);
+        generator.generate_batch(64);
+
+        // Clock is 5ms behind an exhausted millisecond: 6ms until the next one
+        generator.clock().set(EPOCH + 4_995);
+        assert_eq!(
+            generator.try_generate_now(),
+            Err(SnowIDError::SequenceExhausted {
+                retry_after: Duration::from_millis(6)
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_generate_now_does_not_wait_for_regression() {
+        let generator = generator(ClockRegressionPolicy::Wait { tolerance_ms: 10 });
+        generator.generate();
+
+        generator.clock().set(EPOCH + 4_997);
+        assert_eq!(
+            generator.try_generate_now(),
+            Err(SnowIDError::ClockMovedBackwards { delta: 3 })
+        );
+    }
+
+    #[test]
+    fn test_try_generate_now_shares_state_with_generate() {
+        let generator = generator(ClockRegressionPolicy::Clamp);
+        let first = generator.generate();
+        let second = generator.try_generate_now().unwrap();
+        let third = generator.generate();
+        assert!(first < second && second < third);
+    }
+}
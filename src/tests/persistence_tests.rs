@@ -0,0 +1,198 @@
+#[cfg(test)]
+mod tests {
+    use crate::tests::{EPOCH, ManualClock};
+    use crate::*;
+    use std::fs;
+    use std::io::ErrorKind;
+    use std::path::PathBuf;
+    use std::time::{Duration, Instant};
+
+    const START: u64 = EPOCH + 10_000;
+
+    /// Fresh state file path, unique per test and process
+    fn state_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("snowid-{}-{name}.state", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn persisted_generator(
+        path: &PathBuf,
+        policy: ClockRegressionPolicy,
+        now: u64,
+    ) -> Result<SnowID<ManualClock>, SnowIDError> {
+        let cfg = SnowIDConfig::builder()
+            .clock_regression_policy(policy)
+            .build()
+            .unwrap();
+        SnowID::with_clock(1, cfg, ManualClock::new(now))
+            .unwrap()
+            .with_state_file(StateFile::new(path).flush_interval(Duration::from_secs(1)))
+    }
+
+    fn read_mark(path: &PathBuf) -> u64 {
+        fs::read_to_string(path).unwrap().trim().parse().unwrap()
+    }
+
+    /// Wait for the background flusher to write `expected`
+    fn wait_for_mark(path: &PathBuf, expected: u64) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while read_mark(path) != expected {
+            assert!(Instant::now() < deadline, "mark never reached {expected}");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_mark_written_once_per_flush_interval() {
+        let path = state_path("flush");
+        let generator = persisted_generator(&path, ClockRegressionPolicy::Clamp, START).unwrap();
+        // Opening records the current time so generation can start right away
+        assert_eq!(read_mark(&path), START);
+
+        generator.clock().advance(999);
+        generator.generate();
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(read_mark(&path), START);
+
+        generator.clock().advance(1);
+        generator.generate_batch(10);
+        wait_for_mark(&path, START + 1_000);
+
+        // Dropping the generator flushes the latest mark
+        generator.clock().advance(10);
+        generator.generate();
+        drop(generator);
+        assert_eq!(read_mark(&path), START + 1_010);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_generation_waits_for_lagging_flush() {
+        let path = state_path("lagging");
+        let generator = persisted_generator(&path, ClockRegressionPolicy::Clamp, START).unwrap();
+
+        // A jump past the safety margin is not covered until the flusher writes a new mark
+        generator.clock().advance(5_000);
+        assert_eq!(
+            generator.try_generate_now(),
+            Err(SnowIDError::PersistenceBehind)
+        );
+        let id = generator.try_generate().unwrap();
+        assert_eq!(generator.extract.timestamp(id), START + 5_000 - EPOCH);
+        assert_eq!(read_mark(&path), START + 5_000);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_failed_flush_is_reported() {
+        let dir = std::env::temp_dir().join(format!("snowid-{}-failing", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("snowid.state");
+        let generator = persisted_generator(&path, ClockRegressionPolicy::Clamp, START).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        generator.clock().advance(5_000);
+        match generator.try_generate() {
+            Err(SnowIDError::Persistence { kind, .. }) => assert_eq!(kind, ErrorKind::NotFound),
+            other => panic!("expected a persistence error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_safety_margin_must_cover_flush_interval() {
+        let path = state_path("margin");
+        let file = StateFile::new(&path)
+            .flush_interval(Duration::from_secs(5))
+            .safety_margin(Duration::from_secs(1));
+        match SnowID::new(1).unwrap().with_state_file(file) {
+            Err(SnowIDError::Persistence { kind, .. }) => {
+                assert_eq!(kind, ErrorKind::InvalidInput)
+            }
+            other => panic!("expected a persistence error, got {other:?}"),
+        }
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_restart_behind_mark_fails_or_waits() {
+        let path = state_path("restart");
+        fs::write(&path, format!("{START}\n")).unwrap();
+
+        // Default safety margin is 2s past the mark
+        let failing =
+            persisted_generator(&path, ClockRegressionPolicy::Fail, START + 1_000).unwrap();
+        assert_eq!(
+            failing.try_generate(),
+            Err(SnowIDError::ClockMovedBackwards { delta: 999 })
+        );
+        // Opening advanced the mark to the floor; start the second run from the same state
+        drop(failing);
+        assert_eq!(read_mark(&path), START + 2_000);
+        fs::write(&path, format!("{START}\n")).unwrap();
+
+        let clamping =
+            persisted_generator(&path, ClockRegressionPolicy::Clamp, START + 1_000).unwrap();
+        assert_eq!(
+            clamping.try_generate_now(),
+            Err(SnowIDError::SequenceExhausted {
+                retry_after: Duration::from_millis(1_000)
+            })
+        );
+
+        clamping.clock().set(START + 2_000);
+        let id = clamping.try_generate_now().unwrap();
+        assert_eq!(clamping.extract.timestamp(id), START + 2_000 - EPOCH);
+        assert_eq!(clamping.extract.sequence(id), 0);
+        assert_eq!(read_mark(&path), START + 2_000);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_restart_ahead_of_mark_issues_immediately() {
+        let path = state_path("ahead");
+        fs::write(&path, format!("{START}\n")).unwrap();
+
+        let generator =
+            persisted_generator(&path, ClockRegressionPolicy::Fail, START + 60_000).unwrap();
+        let id = generator.try_generate().unwrap();
+        assert_eq!(generator.extract.timestamp(id), START + 60_000 - EPOCH);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mark_past_layout_end_is_rejected() {
+        let path = state_path("past-end");
+        let cfg = SnowIDConfig::default();
+        fs::write(&path, format!("{}\n", cfg.expires_at_millis())).unwrap();
+
+        // The floor is the mark plus the 2s safety margin, all of it past the last tick
+        let max = cfg.timestamp_mask();
+        assert_eq!(
+            persisted_generator(&path, ClockRegressionPolicy::Clamp, START).unwrap_err(),
+            SnowIDError::TimestampOverflow {
+                ticks: max + 2_000,
+                max,
+            }
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_state_file() {
+        let path = state_path("corrupt");
+        fs::write(&path, "not a timestamp").unwrap();
+
+        match persisted_generator(&path, ClockRegressionPolicy::Clamp, START) {
+            Err(SnowIDError::Persistence { kind, .. }) => assert_eq!(kind, ErrorKind::InvalidData),
+            other => panic!("expected a persistence error, got {other:?}"),
+        }
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use crate::tests::{EPOCH, ManualClock};
+    use crate::*;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    fn at(millis: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(EPOCH + millis)
+    }
+
+    /// IDs from three nodes over 1..=10ms, several per millisecond
+    fn issued() -> (SnowIDExtractor, Vec<u64>) {
+        let clock = ManualClock::new(EPOCH);
+        let generators: Vec<_> = (0..3)
+            .map(|node| SnowID::with_clock(node, SnowIDConfig::default(), &clock).unwrap())
+            .collect();
+        let mut ids = Vec::new();
+        for _ in 1..=10 {
+            clock.advance(1);
+            for generator in &generators {
+                ids.extend(generator.generate_batch(3));
+            }
+        }
+        (generators[0].extract, ids)
+    }
+
+    #[test]
+    fn test_id_range_matches_timestamps_exactly() {
+        let (extract, ids) = issued();
+        let range = extract.id_range(at(3), at(6)).unwrap();
+
+        let by_range: Vec<_> = ids.iter().filter(|id| range.contains(id)).collect();
+        let by_timestamp: Vec<_> = ids
+            .iter()
+            .filter(|&&id| (3..=6).contains(&extract.timestamp(id)))
+            .collect();
+        assert_eq!(by_range, by_timestamp);
+        assert_eq!(by_range.len(), 4 * 3 * 3);
+    }
+
+    #[test]
+    fn test_node_restricted_bounds() {
+        let (extract, ids) = issued();
+        let min = extract.min_id_at_node(at(5), 1).unwrap();
+        let max = extract.max_id_at_node(at(5), 1).unwrap();
+
+        let by_bounds: Vec<_> = ids.iter().filter(|&&id| min <= id && id <= max).collect();
+        let expected: Vec<_> = ids
+            .iter()
+            .filter(|&&id| extract.timestamp(id) == 5 && extract.node(id) == 1)
+            .collect();
+        assert_eq!(by_bounds, expected);
+        assert_eq!(by_bounds.len(), 3);
+
+        assert_eq!(extract.min_id_at_node(at(5), 1024), None);
+    }
+
+    #[test]
+    fn test_id_range_rejects_unrepresentable_bounds() {
+        let extract = SnowIDExtractor::new(SnowIDConfig::default());
+        assert_eq!(extract.id_range(at(6), at(3)), None);
+        assert_eq!(extract.id_range(UNIX_EPOCH, at(3)), None);
+        assert_eq!(extract.id_range(at(0), at(0)), Some(0..=(1 << 22) - 1));
+    }
+}
@@ -66,27 +66,27 @@ fn test_sequence_overflow_handling() {
             let snowid = generator.generate();
             let (ts, _, sequence) = generator.extract.decompose(snowid);
 
-            if let (Some(prev_ts), Some(prev_seq)) = (last_ts, last_sequence) {
-                if ts == prev_ts {
-                    // Within same millisecond, sequence should increment
+            if let (Some(prev_ts), Some(prev_seq)) = (last_ts, last_sequence)
+                && ts == prev_ts
+            {
+                // Within same millisecond, sequence should increment
+                assert!(
+                    sequence > prev_seq,
+                    "Sequence should increment within same millisecond"
+                );
+
+                // Check if we've hit the max sequence
+                if sequence >= generator.config.max_sequence_id() {
+                    // Next ID should be in a new millisecond
+                    let next_id = generator.generate();
+                    let (next_ts, _, next_seq) = generator.extract.decompose(next_id);
                     assert!(
-                        sequence > prev_seq,
-                        "Sequence should increment within same millisecond"
+                        next_ts > ts,
+                        "Timestamp should advance on sequence overflow"
                     );
-
-                    // Check if we've hit the max sequence
-                    if sequence >= generator.config.max_sequence_id() {
-                        // Next ID should be in a new millisecond
-                        let next_id = generator.generate();
-                        let (next_ts, _, next_seq) = generator.extract.decompose(next_id);
-                        assert!(
-                            next_ts > ts,
-                            "Timestamp should advance on sequence overflow"
-                        );
-                        assert_eq!(next_seq, 0, "Sequence should reset to 0 on overflow");
-                        overflow_handled = true;
-                        break;
-                    }
+                    assert_eq!(next_seq, 0, "Sequence should reset to 0 on overflow");
+                    overflow_handled = true;
+                    break;
                 }
             }
 
@@ -6,7 +6,7 @@ mod tests {
     fn test_wait_next_millis_progresses() {
         let generator = SnowID::new(1).unwrap();
         let from = generator.get_time_since_epoch();
-        let next = generator.wait_next_millis(from, 1);
+        let next = generator.wait_next_millis(from, 1).unwrap();
         assert!(next > from);
     }
 
@@ -16,10 +16,24 @@ fn test_wait_next_millis_progresses_no_spin() {
             .enable_spin(false)
             .spin_loops(0)
             .spin_yield_every(0)
-            .build();
+            .build()
+            .unwrap();
         let generator = SnowID::with_config(1, cfg).unwrap();
         let from = generator.get_time_since_epoch();
-        let next = generator.wait_next_millis(from, 1);
+        let next = generator.wait_next_millis(from, 1).unwrap();
         assert!(next > from);
     }
+
+    #[test]
+    fn test_idle_generator_stamps_current_millisecond() {
+        let generator = SnowID::new(1).unwrap();
+        let first = generator.generate();
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let before = generator.get_time_since_epoch();
+        let second = generator.generate();
+
+        assert!(generator.extract.timestamp(second) >= before);
+        assert!(generator.extract.timestamp(second) > generator.extract.timestamp(first));
+    }
 }
@@ -0,0 +1,253 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use crate::{
+    Base62DecodeError, Base62Str, Clock, SnowID, SnowIDError, SnowId, SystemClock, base62_decode,
+};
+
+/// A SnowID tagged with the entity type `T` it identifies
+///
+/// `T` is a marker type that only exists at compile time, so an `Id<Order>` cannot be passed
+/// where an `Id<User>` is expected, while the value itself is a plain `u64`: same size,
+/// same layout and no runtime cost. Issue them with [`SnowID::typed`].
+///
+/// Strings follow the same rule as [`SnowId`]: `Display` and `FromStr` use the decimal form,
+/// and base62 goes through [`Id::to_base62`] and [`Id::from_base62`].
+#[repr(transparent)]
+pub struct Id<T> {
+    value: u64,
+    // `fn() -> T` keeps `Id<T>` `Send`, `Sync` and covariant regardless of `T`
+    entity: PhantomData<fn() -> T>,
+}
+
+impl<T> Id<T> {
+    /// Tag a raw ID as identifying a `T`
+    #[inline(always)]
+    pub const fn from_raw(value: u64) -> Self {
+        Self {
+            value,
+            entity: PhantomData,
+        }
+    }
+
+    /// Parse the decimal form, as printed by `Display`
+    pub fn from_decimal(input: &str) -> Result<Self, ParseIntError> {
+        input.parse().map(Self::from_raw)
+    }
+
+    /// Decode the base62 form, as produced by `to_base62`
+    pub fn from_base62(encoded: &str) -> Result<Self, Base62DecodeError> {
+        base62_decode(encoded).map(Self::from_raw)
+    }
+
+    /// Raw numeric value
+    #[inline(always)]
+    pub const fn as_u64(&self) -> u64 {
+        self.value
+    }
+
+    /// Base62 representation, without the entity prefix
+    #[inline]
+    pub fn to_base62(&self) -> Base62Str {
+        Base62Str::new(self.value)
+    }
+}
+
+// Manual impls so the traits do not require `T` to implement them
+
+impl<T> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Id<T> {}
+
+impl<T> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T> Eq for Id<T> {}
+
+impl<T> PartialOrd for Id<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Id<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T> Hash for Id<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Id").field(&self.value).finish()
+    }
+}
+
+impl<T> fmt::Display for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)
+    }
+}
+
+/// Parses the decimal form, like [`SnowId`]; see [`Id::from_base62`] for base62
+impl<T> FromStr for Id<T> {
+    type Err = ParseIntError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::from_decimal(input)
+    }
+}
+
+impl<T> From<u64> for Id<T> {
+    fn from(value: u64) -> Self {
+        Self::from_raw(value)
+    }
+}
+
+impl<T> From<Id<T>> for u64 {
+    fn from(id: Id<T>) -> Self {
+        id.value
+    }
+}
+
+/// Drops the entity type, e.g. to access components through [`SnowId`]
+impl<T> From<Id<T>> for SnowId {
+    fn from(id: Id<T>) -> Self {
+        SnowId::from_raw(id.value)
+    }
+}
+
+/// A view of a generator that issues [`Id<T>`] values, obtained from [`SnowID::typed`]
+///
+/// It borrows the generator, so any number of entity types can share one generator and
+/// its node ID without their IDs ever colliding.
+pub struct TypedSnowID<'a, T, C = SystemClock> {
+    generator: &'a SnowID<C>,
+    entity: PhantomData<fn() -> T>,
+}
+
+impl<T, C: Clock> TypedSnowID<'_, T, C> {
+    /// Generate a new ID for a `T`
+    ///
+    /// # Panics
+    /// Panics under the same conditions as [`SnowID::generate`].
+    #[inline]
+    pub fn generate(&self) -> Id<T> {
+        Id::from_raw(self.generator.generate())
+    }
+
+    /// Generate a new ID for a `T`, reporting errors instead of panicking
+    #[inline]
+    pub fn try_generate(&self) -> Result<Id<T>, SnowIDError> {
+        Ok(Id::from_raw(self.generator.try_generate()?))
+    }
+
+    /// The underlying generator
+    #[inline]
+    pub fn generator(&self) -> &SnowID<C> {
+        self.generator
+    }
+}
+
+impl<T, C> Clone for TypedSnowID<'_, T, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, C> Copy for TypedSnowID<'_, T, C> {}
+
+impl<T, C> fmt::Debug for TypedSnowID<'_, T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedSnowID")
+            .field("entity", &std::any::type_name::<T>())
+            .field("node_id", &self.generator.node_id)
+            .finish()
+    }
+}
+
+impl<C: Clock> SnowID<C> {
+    /// Borrow this generator as one that issues [`Id<T>`] for the marker type `T`
+    #[inline]
+    pub fn typed<T>(&self) -> TypedSnowID<'_, T, C> {
+        TypedSnowID {
+            generator: self,
+            entity: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use std::mem::{align_of, size_of};
+
+    struct User;
+    struct Order;
+
+    #[test]
+    fn test_zero_overhead() {
+        assert_eq!(size_of::<Id<User>>(), size_of::<u64>());
+        assert_eq!(align_of::<Id<User>>(), align_of::<u64>());
+        assert_eq!(size_of::<Option<Id<User>>>(), size_of::<Option<u64>>());
+    }
+
+    #[test]
+    fn test_typed_generation() {
+        let generator = SnowID::new(7).unwrap();
+        let users = generator.typed::<User>();
+        let orders = generator.typed::<Order>();
+
+        let ids: Vec<Id<User>> = (0..100).map(|_| users.generate()).collect();
+        let order = orders.try_generate().unwrap();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(ids.iter().collect::<BTreeSet<_>>().len(), 100);
+        // Entity types share the generator's sequence, so their IDs never collide
+        assert!(order.as_u64() > ids[99].as_u64());
+
+        let snow_id = SnowId::from(ids[0]);
+        assert_eq!(snow_id.as_u64(), ids[0].as_u64());
+        assert_eq!(snow_id.node(&generator.config), 7);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let id = Id::<User>::from_raw(42);
+        assert_eq!(u64::from(id), 42);
+        assert_eq!(Id::<User>::from(42), id);
+        assert_eq!(id.to_string(), "42");
+        assert_eq!(format!("{id:?}"), "Id(42)");
+        assert_eq!("42".parse::<Id<User>>(), Ok(id));
+        assert_eq!(Id::<User>::from_decimal("42"), Ok(id));
+        assert!("usr_42".parse::<Id<User>>().is_err());
+
+        assert_eq!(id.to_base62(), "g");
+        assert_eq!(Id::<User>::from_base62("g").unwrap(), id);
+        // Same parsing rule as `SnowId`: digit-only strings are decimal unless asked otherwise
+        assert_eq!(
+            "123".parse::<Id<User>>().unwrap().as_u64(),
+            "123".parse::<SnowId>().unwrap().as_u64()
+        );
+        assert_eq!(
+            Id::<User>::from_base62("123").unwrap().as_u64(),
+            SnowId::from_base62("123").unwrap().as_u64()
+        );
+    }
+}
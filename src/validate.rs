@@ -0,0 +1,234 @@
+use std::fmt;
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::{Clock, SnowIDExtractor, UnixMillis};
+
+const DEFAULT_MAX_CLOCK_SKEW: Duration = Duration::from_secs(1);
+
+/// Plausibility rules applied by [`SnowIDExtractor::validate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationRules {
+    max_clock_skew: Duration,
+    not_before: Option<u64>,
+    allowed_nodes: Option<Vec<u16>>,
+}
+
+impl ValidationRules {
+    /// Rules that accept any node and any timestamp up to 1s ahead of the clock
+    pub fn new() -> Self {
+        Self {
+            max_clock_skew: DEFAULT_MAX_CLOCK_SKEW,
+            not_before: None,
+            allowed_nodes: None,
+        }
+    }
+
+    /// Set how far ahead of the validating clock a timestamp may be, to absorb clock skew
+    /// between generators and validators. Defaults to 1s.
+    pub fn max_clock_skew(mut self, skew: Duration) -> Self {
+        self.max_clock_skew = skew;
+        self
+    }
+
+    /// Reject IDs issued before `at`, e.g. the launch of the service. Times before the
+    /// Unix epoch are ignored.
+    pub fn not_before(mut self, at: impl UnixMillis) -> Self {
+        self.not_before = at.to_unix_millis();
+        self
+    }
+
+    /// Only accept IDs issued by the given node IDs
+    pub fn allowed_nodes(mut self, nodes: impl IntoIterator<Item = u16>) -> Self {
+        let mut nodes: Vec<u16> = nodes.into_iter().collect();
+        nodes.sort_unstable();
+        nodes.dedup();
+        self.allowed_nodes = Some(nodes);
+        self
+    }
+}
+
+impl Default for ValidationRules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A single reason an ID failed validation
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum IdProblem {
+    /// Bits outside the configured layout (e.g. a reserved sign bit) are set
+    #[error("bits {bits:#x} outside the layout are set")]
+    ReservedBitsSet { bits: u64 },
+    /// Timestamp field does not convert to Unix milliseconds without overflowing
+    #[error("timestamp of {ticks} ticks overflows the u64 millisecond range")]
+    TimestampOutOfRange { ticks: u64 },
+    /// Timestamp is earlier than the configured lower bound (both in Unix milliseconds)
+    #[error("timestamp {timestamp} is before {not_before}")]
+    TimestampTooOld { timestamp: u64, not_before: u64 },
+    /// Timestamp is further ahead of the clock than the allowed skew (both in Unix milliseconds)
+    #[error("timestamp {timestamp} is in the future (now {now})")]
+    TimestampInFuture { timestamp: u64, now: u64 },
+    /// Node ID is not in the allowed set
+    #[error("node {node} is not allowed")]
+    NodeNotAllowed { node: u16 },
+}
+
+/// Error returned by [`SnowIDExtractor::validate`], listing every problem found
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub struct InvalidId {
+    /// The rejected ID
+    pub id: u64,
+    /// Every rule the ID broke, in the order they were checked
+    pub problems: Vec<IdProblem>,
+}
+
+impl fmt::Display for InvalidId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid SnowID {}", self.id)?;
+        for (index, problem) in self.problems.iter().enumerate() {
+            f.write_str(if index == 0 { ": " } else { ", " })?;
+            write!(f, "{problem}")?;
+        }
+        Ok(())
+    }
+}
+
+impl SnowIDExtractor {
+    /// Check that an ID received from outside is plausible for this configuration
+    ///
+    /// Node and sequence values always fit their fields, so only bits outside the layout,
+    /// the timestamp against `clock`, and the node against the allowed set can be checked.
+    /// Arithmetic on the timestamp is checked, so hostile input is reported, never wrapped.
+    ///
+    /// # Returns
+    /// * `Result<(), InvalidId>` - `Ok` if the ID passes every rule, otherwise all problems found
+    pub fn validate(
+        &self,
+        id: u64,
+        rules: &ValidationRules,
+        clock: impl Clock,
+    ) -> Result<(), InvalidId> {
+        let mut problems = Vec::new();
+
+        let total_bits = self.config().total_bits();
+        let outside = match total_bits {
+            64 => 0,
+            bits => id & !((1u64 << bits) - 1),
+        };
+        if outside != 0 {
+            problems.push(IdProblem::ReservedBitsSet { bits: outside });
+        }
+
+        match self.checked_unix_millis(id) {
+            Some(timestamp) => {
+                if let Some(not_before) = rules.not_before
+                    && timestamp < not_before
+                {
+                    problems.push(IdProblem::TimestampTooOld {
+                        timestamp,
+                        not_before,
+                    });
+                }
+                let now = clock.now_millis();
+                if timestamp > now.saturating_add(rules.max_clock_skew.as_millis() as u64) {
+                    problems.push(IdProblem::TimestampInFuture { timestamp, now });
+                }
+            }
+            None => problems.push(IdProblem::TimestampOutOfRange {
+                ticks: self.ticks(id),
+            }),
+        }
+
+        let node = self.node(id);
+        if let Some(allowed) = &rules.allowed_nodes
+            && allowed.binary_search(&node).is_err()
+        {
+            problems.push(IdProblem::NodeNotAllowed { node });
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(InvalidId { id, problems })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SnowIDConfig;
+    use crate::tests::{EPOCH, ManualClock};
+    use std::time::UNIX_EPOCH;
+
+    fn extractor() -> SnowIDExtractor {
+        let config = SnowIDConfig::builder()
+            .reserve_sign_bit(true)
+            .build()
+            .unwrap();
+        SnowIDExtractor::new(config)
+    }
+
+    #[test]
+    fn test_valid_id() {
+        let extract = extractor();
+        let clock = ManualClock::new(EPOCH + 60_000);
+        let id = extract.compose(59_000, 7, 3).unwrap();
+        let rules = ValidationRules::new().allowed_nodes([7, 8]);
+        assert_eq!(extract.validate(id, &rules, &clock), Ok(()));
+    }
+
+    #[test]
+    fn test_every_problem_is_reported() {
+        let extract = extractor();
+        let clock = ManualClock::new(EPOCH + 60_000);
+        let id = extract.compose(62_000, 9, 0).unwrap() | 1 << 63;
+        let rules = ValidationRules::new()
+            .not_before(UNIX_EPOCH + Duration::from_millis(EPOCH + 70_000))
+            .allowed_nodes([7, 8]);
+
+        let err = extract.validate(id, &rules, &clock).unwrap_err();
+        assert_eq!(
+            err.problems,
+            vec![
+                IdProblem::ReservedBitsSet { bits: 1 << 63 },
+                IdProblem::TimestampTooOld {
+                    timestamp: EPOCH + 62_000,
+                    not_before: EPOCH + 70_000
+                },
+                IdProblem::TimestampInFuture {
+                    timestamp: EPOCH + 62_000,
+                    now: EPOCH + 60_000
+                },
+                IdProblem::NodeNotAllowed { node: 9 },
+            ]
+        );
+        assert!(
+            err.to_string()
+                .starts_with(&format!("Invalid SnowID {id}: bits 0x8000000000000000"))
+        );
+    }
+
+    #[test]
+    fn test_clock_skew_tolerance() {
+        let extract = SnowIDExtractor::new(SnowIDConfig::default());
+        let clock = ManualClock::new(EPOCH + 60_000);
+        let id = extract.compose(61_000, 0, 0).unwrap();
+
+        assert_eq!(
+            extract.validate(id, &ValidationRules::new(), &clock),
+            Ok(())
+        );
+        let strict = ValidationRules::new().max_clock_skew(Duration::ZERO);
+        assert!(extract.validate(id, &strict, &clock).is_err());
+
+        // A full 64-bit layout has no bits outside its fields
+        assert!(
+            extract
+                .validate(u64::MAX, &ValidationRules::new(), &clock)
+                .is_err_and(|err| err.problems.len() == 1)
+        );
+    }
+}
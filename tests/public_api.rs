@@ -0,0 +1,34 @@
+//! Checks that the types needed to handle configuration errors are reachable from outside the crate
+
+use std::time::Duration;
+
+use snowid::{CapacityRequirements, SnowIDConfig, SnowIDConfigBuilder, SnowIDConfigError};
+
+#[test]
+fn test_config_errors_are_nameable() {
+    let builder: SnowIDConfigBuilder = SnowIDConfig::builder();
+    let result: Result<SnowIDConfig, SnowIDConfigError> = builder
+        .timestamp_bits(42)
+        .unwrap()
+        .node_bits(16)
+        .unwrap()
+        .sequence_bits(16)
+        .unwrap()
+        .build();
+    assert!(matches!(
+        result,
+        Err(SnowIDConfigError::LayoutOverflow { max_bits: 64, .. })
+    ));
+
+    let far_future = SnowIDConfig::builder().epoch(u64::MAX / 2).build();
+    assert!(matches!(
+        far_future,
+        Err(SnowIDConfigError::FutureEpoch { .. })
+    ));
+
+    let requirements = CapacityRequirements::new(100_000, 1_000, Duration::from_secs(1));
+    assert!(matches!(
+        SnowIDConfig::recommend(requirements),
+        Err(SnowIDConfigError::InvalidNodeBits { bits: 17 })
+    ));
+}